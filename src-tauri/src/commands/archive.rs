@@ -0,0 +1,541 @@
+use super::encoding;
+use super::error::{CommandError, CommandResult, ErrorKind, ResultExt};
+use super::file_view::is_binary;
+use super::fs::{remove_path_permanently, temp_sibling_path};
+use super::trash::trash_entry;
+use super::transfer::{
+    conflict_free_path, generate_operation_id, ConflictPolicy, ProgressReporter, TransferEntry, TransferResult,
+};
+use super::workspace::{ensure_allowed, ensure_entry_allowed};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use tauri::AppHandle;
+
+const MAX_ARCHIVE_ENTRIES: usize = 100_000;
+const MAX_ENTRY_BYTES: u64 = 4 * 1024 * 1024;
+const COPY_BUFFER_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchiveEntry {
+    pub path: String,
+    #[serde(rename = "isDirectory")]
+    pub is_directory: bool,
+    #[serde(rename = "isSymlink")]
+    pub is_symlink: bool,
+    pub size: u64,
+    #[serde(rename = "compressedSize")]
+    pub compressed_size: Option<u64>,
+    pub mtime: Option<u64>,
+    pub mode: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArchiveListing {
+    pub path: String,
+    pub format: String,
+    pub entries: Vec<ArchiveEntry>,
+    pub truncated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArchiveEntryContent {
+    pub path: String,
+    pub size: u64,
+    #[serde(rename = "isBinary")]
+    pub is_binary: bool,
+    pub encoding: Option<String>,
+    pub text: Option<String>,
+    pub bytes: Option<Vec<u8>>,
+    pub truncated: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    fn label(self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::Tar => "tar",
+            ArchiveFormat::TarGz => "tar.gz",
+        }
+    }
+}
+
+fn detect_format(path: &Path) -> CommandResult<ArchiveFormat> {
+    let mut head = Vec::with_capacity(512);
+    File::open(path)
+        .and_then(|file| file.take(512).read_to_end(&mut head))
+        .with_path(path)?;
+
+    if head.starts_with(b"PK\x03\x04") || head.starts_with(b"PK\x05\x06") {
+        return Ok(ArchiveFormat::Zip);
+    }
+    if head.starts_with(b"\x1f\x8b") {
+        return Ok(ArchiveFormat::TarGz);
+    }
+    if head.len() >= 262 && &head[257..262] == b"ustar" {
+        return Ok(ArchiveFormat::Tar);
+    }
+    if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("tar")) {
+        return Ok(ArchiveFormat::Tar);
+    }
+    Err(CommandError::new(ErrorKind::Unsupported, "Unsupported archive format").with_path(path))
+}
+
+fn archive_error(path: &Path, error: impl std::fmt::Display) -> CommandError {
+    CommandError::new(ErrorKind::Io, format!("Failed to read archive: {}", error)).with_path(path)
+}
+
+fn normalize_name(name: &str) -> String {
+    name.replace('\\', "/").trim_end_matches('/').to_string()
+}
+
+fn safe_relative(name: &str) -> Option<PathBuf> {
+    let mut relative = PathBuf::new();
+    for part in name.split('/') {
+        match part {
+            "" | "." => continue,
+            ".." => return None,
+            part if part.contains(':') => return None,
+            part => relative.push(part),
+        }
+    }
+    if name.starts_with('/') || relative.as_os_str().is_empty() {
+        return None;
+    }
+    relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+        .then_some(relative)
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+fn zip_mtime(time: zip::DateTime) -> Option<u64> {
+    let days = days_from_civil(time.year() as i64, time.month() as i64, time.day() as i64);
+    let secs = days * 86400 + time.hour() as i64 * 3600 + time.minute() as i64 * 60 + time.second() as i64;
+    u64::try_from(secs).ok().map(|s| s * 1000)
+}
+
+fn for_each_entry<F>(path: &Path, format: ArchiveFormat, mut visit: F) -> CommandResult<()>
+where
+    F: FnMut(ArchiveEntry, &mut dyn Read) -> CommandResult<bool>,
+{
+    let file = File::open(path).with_path(path)?;
+    match format {
+        ArchiveFormat::Zip => {
+            let mut archive = zip::ZipArchive::new(file).map_err(|e| archive_error(path, e))?;
+            for index in 0..archive.len() {
+                let mut zip_file = archive.by_index(index).map_err(|e| archive_error(path, e))?;
+                let entry = ArchiveEntry {
+                    path: normalize_name(zip_file.name()),
+                    is_directory: zip_file.is_dir(),
+                    is_symlink: zip_file.is_symlink(),
+                    size: zip_file.size(),
+                    compressed_size: Some(zip_file.compressed_size()),
+                    mtime: zip_file.last_modified().and_then(zip_mtime),
+                    mode: zip_file.unix_mode().map(|m| m & 0o7777),
+                };
+                if !visit(entry, &mut zip_file)? {
+                    break;
+                }
+            }
+        }
+        ArchiveFormat::Tar | ArchiveFormat::TarGz => {
+            let reader: Box<dyn Read> = match format {
+                ArchiveFormat::TarGz => Box::new(flate2::read::GzDecoder::new(file)),
+                _ => Box::new(file),
+            };
+            let mut archive = tar::Archive::new(reader);
+            for tar_entry in archive.entries().map_err(|e| archive_error(path, e))? {
+                let mut tar_entry = tar_entry.map_err(|e| archive_error(path, e))?;
+                let header = tar_entry.header();
+                let kind = header.entry_type();
+                if !(kind.is_file() || kind.is_dir() || kind.is_symlink() || kind.is_hard_link()) {
+                    continue;
+                }
+                let entry = ArchiveEntry {
+                    path: normalize_name(&tar_entry.path().map_err(|e| archive_error(path, e))?.to_string_lossy()),
+                    is_directory: kind.is_dir(),
+                    is_symlink: kind.is_symlink() || kind.is_hard_link(),
+                    size: header.size().unwrap_or(0),
+                    compressed_size: None,
+                    mtime: header.mtime().ok().map(|s| s * 1000),
+                    mode: header.mode().ok().map(|m| m & 0o7777),
+                };
+                if !visit(entry, &mut tar_entry)? {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn open_archive(path: &str) -> CommandResult<(PathBuf, ArchiveFormat)> {
+    let resolved = ensure_allowed(path)?;
+    if !resolved.is_file() {
+        return Err(CommandError::invalid_input("Archive must be a file").with_path(&resolved));
+    }
+    let format = detect_format(&resolved)?;
+    Ok((resolved, format))
+}
+
+fn is_selected(entry: &str, selection: &[String]) -> bool {
+    selection.is_empty()
+        || selection.iter().any(|selected| {
+            let selected = normalize_name(selected);
+            entry == selected || entry.starts_with(&format!("{}/", selected))
+        })
+}
+
+fn extract_target(root: &Path, relative: &Path) -> CommandResult<Option<PathBuf>> {
+    let mut target = root.to_path_buf();
+    let mut components = relative.components().peekable();
+    while let Some(component) = components.next() {
+        target.push(component);
+        if components.peek().is_none() {
+            break;
+        }
+        match fs::symlink_metadata(&target) {
+            Ok(metadata) if metadata.is_dir() => {}
+            Ok(_) => return Ok(None),
+            Err(_) => fs::create_dir(&target).with_path(&target)?,
+        }
+    }
+    Ok(Some(ensure_entry_allowed(&target)?))
+}
+
+fn write_entry(
+    reader: &mut dyn Read,
+    target: &Path,
+    mode: Option<u32>,
+    reporter: &mut ProgressReporter,
+) -> CommandResult<()> {
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(target)
+        .with_path(target)?;
+    let mut buffer = vec![0u8; COPY_BUFFER_SIZE];
+    loop {
+        let read = reader.read(&mut buffer).with_path(target)?;
+        if read == 0 {
+            break;
+        }
+        file.write_all(&buffer[..read]).with_path(target)?;
+        reporter.advance(target, read as u64);
+    }
+
+    #[cfg(unix)]
+    if let Some(mode) = mode.filter(|m| *m != 0) {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(target, fs::Permissions::from_mode(mode & 0o777)).with_path(target)?;
+    }
+    #[cfg(not(unix))]
+    let _ = mode;
+    Ok(())
+}
+
+fn replace_entry(
+    reader: &mut dyn Read,
+    target: &Path,
+    mode: Option<u32>,
+    reporter: &mut ProgressReporter,
+) -> CommandResult<()> {
+    if fs::symlink_metadata(target).is_ok_and(|m| m.is_dir()) {
+        return Err(CommandError::conflict("Cannot replace a folder with a file").with_path(target));
+    }
+    let staged = temp_sibling_path(target);
+    let swapped = write_entry(reader, &staged, mode, reporter)
+        .and_then(|_| trash_entry(target))
+        .and_then(|_| fs::rename(&staged, target).with_path(target));
+    if swapped.is_err() {
+        let _ = remove_path_permanently(&staged);
+    }
+    swapped
+}
+
+fn extract(
+    archive: &Path,
+    format: ArchiveFormat,
+    root: &Path,
+    selection: &[String],
+    policy: ConflictPolicy,
+    reporter: &mut ProgressReporter,
+) -> CommandResult<Vec<TransferEntry>> {
+    let mut results = Vec::new();
+    let mut completed_bytes = 0;
+
+    for_each_entry(archive, format, |entry, reader| {
+        if !is_selected(&entry.path, selection) {
+            return Ok(true);
+        }
+        let mut result = |destination: Option<&Path>, status: &str| {
+            results.push(TransferEntry {
+                source: entry.path.clone(),
+                destination: destination.map(|d| d.to_string_lossy().to_string()).unwrap_or_default(),
+                status: status.to_string(),
+            })
+        };
+
+        let target = match safe_relative(&entry.path) {
+            Some(relative) => extract_target(root, &relative)?,
+            None => None,
+        };
+        let mut target = match target {
+            Some(target) => target,
+            None => {
+                result(None, "blocked");
+                return Ok(true);
+            }
+        };
+        if entry.is_symlink {
+            result(Some(&target), "skipped");
+            return Ok(true);
+        }
+        if entry.is_directory {
+            fs::create_dir_all(&target).with_path(&target)?;
+            return Ok(true);
+        }
+
+        if fs::symlink_metadata(&target).is_ok() {
+            match policy {
+                ConflictPolicy::Skip => {
+                    result(Some(&target), "skipped");
+                    return Ok(true);
+                }
+                ConflictPolicy::Rename => target = conflict_free_path(&target),
+                ConflictPolicy::Overwrite => {
+                    replace_entry(reader, &target, entry.mode, reporter)?;
+                    completed_bytes += entry.size;
+                    reporter.complete_item(completed_bytes);
+                    result(Some(&target), "extracted");
+                    return Ok(true);
+                }
+                ConflictPolicy::Error => {
+                    return Err(
+                        CommandError::new(ErrorKind::AlreadyExists, "Destination already exists").with_path(&target)
+                    );
+                }
+            }
+        }
+
+        write_entry(reader, &target, entry.mode, reporter)?;
+        completed_bytes += entry.size;
+        reporter.complete_item(completed_bytes);
+        result(Some(&target), "extracted");
+        Ok(true)
+    })?;
+    Ok(results)
+}
+
+fn list_archive(path: &str) -> CommandResult<ArchiveListing> {
+    let (resolved, format) = open_archive(path)?;
+    let mut entries = Vec::new();
+    let mut truncated = false;
+    for_each_entry(&resolved, format, |entry, _| {
+        if entries.len() >= MAX_ARCHIVE_ENTRIES {
+            truncated = true;
+            return Ok(false);
+        }
+        entries.push(entry);
+        Ok(true)
+    })?;
+
+    Ok(ArchiveListing {
+        path: resolved.to_string_lossy().to_string(),
+        format: format.label().to_string(),
+        entries,
+        truncated,
+    })
+}
+
+fn read_entry(path: &str, entry_path: &str, as_bytes: bool) -> CommandResult<ArchiveEntryContent> {
+    let (resolved, format) = open_archive(path)?;
+    let wanted = normalize_name(entry_path);
+    let mut found = None;
+    for_each_entry(&resolved, format, |entry, reader| {
+        if entry.path != wanted {
+            return Ok(true);
+        }
+        if entry.is_directory || entry.is_symlink {
+            return Err(CommandError::invalid_input("Archive entry is not a regular file").with_path(entry_path));
+        }
+        let mut bytes = Vec::new();
+        reader
+            .take(MAX_ENTRY_BYTES)
+            .read_to_end(&mut bytes)
+            .map_err(|e| archive_error(&resolved, e))?;
+        found = Some((entry.size, bytes));
+        Ok(false)
+    })?;
+
+    let (size, bytes) = found.ok_or_else(|| CommandError::not_found("Archive entry not found").with_path(entry_path))?;
+    let truncated = (bytes.len() as u64) < size;
+    let binary = is_binary(&bytes);
+    let mut content = ArchiveEntryContent {
+        path: wanted,
+        size,
+        is_binary: binary,
+        encoding: None,
+        text: None,
+        bytes: None,
+        truncated,
+    };
+    if as_bytes {
+        content.bytes = Some(bytes);
+    } else if !binary {
+        let decoded = encoding::decode(&bytes, None);
+        content.encoding = Some(decoded.encoding.name().to_string());
+        content.text = Some(decoded.text);
+    }
+    Ok(content)
+}
+
+#[tauri::command]
+pub async fn archive_list(path: String) -> CommandResult<ArchiveListing> {
+    tokio::task::spawn_blocking(move || list_archive(&path))
+        .await
+        .map_err(|e| CommandError::other(format!("Archive listing failed: {}", e)))?
+}
+
+#[tauri::command]
+pub async fn archive_read_entry(
+    path: String,
+    entry_path: String,
+    as_bytes: Option<bool>,
+) -> CommandResult<ArchiveEntryContent> {
+    tokio::task::spawn_blocking(move || read_entry(&path, &entry_path, as_bytes.unwrap_or(false)))
+        .await
+        .map_err(|e| CommandError::other(format!("Reading archive entry failed: {}", e)))?
+}
+
+#[tauri::command]
+pub async fn archive_extract(
+    app: AppHandle,
+    path: String,
+    destination_dir: String,
+    entries: Option<Vec<String>>,
+    policy: Option<ConflictPolicy>,
+    operation_id: Option<String>,
+) -> CommandResult<TransferResult> {
+    let operation_id = operation_id.unwrap_or_else(|| generate_operation_id("transfer"));
+    tokio::task::spawn_blocking(move || {
+        let (archive, format) = open_archive(&path)?;
+        let root = ensure_allowed(&destination_dir)?;
+        if !root.is_dir() {
+            return Err(CommandError::invalid_input("Destination must be a directory").with_path(&root));
+        }
+        let selection = entries.unwrap_or_default();
+
+        let mut total_bytes = 0;
+        let mut total_items = 0;
+        for_each_entry(&archive, format, |entry, _| {
+            if !entry.is_directory && is_selected(&entry.path, &selection) {
+                total_bytes += entry.size;
+                total_items += 1;
+            }
+            Ok(true)
+        })?;
+
+        let mut reporter = ProgressReporter::new(app, operation_id.clone(), total_bytes, total_items);
+        let result = extract(&archive, format, &root, &selection, policy.unwrap_or_default(), &mut reporter);
+        reporter.finish();
+        Ok(TransferResult {
+            operation_id,
+            entries: result?,
+        })
+    })
+    .await
+    .map_err(|e| CommandError::other(format!("Extraction failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::workspace::trust_root;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("litcode-archive-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        trust_root(&dir).unwrap()
+    }
+
+    #[test]
+    fn safe_relative_accepts_nested_names() {
+        assert_eq!(safe_relative("a/b/c.txt"), Some(PathBuf::from("a/b/c.txt")));
+        assert_eq!(safe_relative("./a//b/"), Some(PathBuf::from("a/b")));
+    }
+
+    #[test]
+    fn safe_relative_rejects_parent_components() {
+        assert_eq!(safe_relative(".."), None);
+        assert_eq!(safe_relative("../evil.txt"), None);
+        assert_eq!(safe_relative("a/../../evil.txt"), None);
+        assert_eq!(safe_relative("a/b/.."), None);
+    }
+
+    #[test]
+    fn safe_relative_rejects_absolute_paths() {
+        assert_eq!(safe_relative("/etc/passwd"), None);
+        assert_eq!(safe_relative("//server/share"), None);
+        assert_eq!(safe_relative(""), None);
+        assert_eq!(safe_relative("./"), None);
+    }
+
+    #[test]
+    fn safe_relative_rejects_drive_prefixes() {
+        assert_eq!(safe_relative("C:"), None);
+        assert_eq!(safe_relative("C:/Windows/evil.dll"), None);
+        assert_eq!(safe_relative("C:evil.txt"), None);
+        assert_eq!(safe_relative("a/file.txt:stream"), None);
+    }
+
+    #[test]
+    fn extract_target_creates_parent_directories() {
+        let root = scratch_dir("parents");
+        let target = extract_target(&root, Path::new("a/b/c.txt")).unwrap().unwrap();
+        assert_eq!(target, root.join("a/b/c.txt"));
+        assert!(root.join("a/b").is_dir());
+        assert!(!target.exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn extract_target_blocks_file_parents() {
+        let root = scratch_dir("file-parent");
+        fs::write(root.join("a"), "").unwrap();
+        assert_eq!(extract_target(&root, Path::new("a/b.txt")).unwrap(), None);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn extract_target_blocks_symlinked_parents() {
+        let root = scratch_dir("symlink-parent");
+        let outside = scratch_dir("symlink-outside");
+        std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+        assert_eq!(extract_target(&root, Path::new("link/evil.txt")).unwrap(), None);
+        assert_eq!(extract_target(&root, Path::new("link/nested/evil.txt")).unwrap(), None);
+        assert!(!outside.join("nested").exists());
+        fs::remove_dir_all(&root).unwrap();
+        fs::remove_dir_all(&outside).unwrap();
+    }
+}
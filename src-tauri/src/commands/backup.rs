@@ -0,0 +1,271 @@
+use super::config::litcode_dir;
+use super::encoding;
+use super::error::{CommandError, CommandResult, ErrorKind, ResultExt};
+use super::fs::{atomic_write, FileVersion};
+use super::workspace::ensure_allowed;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BufferBackup {
+    #[serde(rename = "bufferId")]
+    pub buffer_id: String,
+    pub path: Option<String>,
+    #[serde(rename = "savedAt")]
+    pub saved_at: u64,
+    pub size: u64,
+    #[serde(rename = "baseVersion")]
+    pub base_version: Option<FileVersion>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredBackup {
+    #[serde(rename = "bufferId")]
+    buffer_id: String,
+    path: Option<String>,
+    #[serde(rename = "savedAt")]
+    saved_at: u64,
+    #[serde(rename = "baseVersion")]
+    base_version: Option<FileVersion>,
+    content: String,
+}
+
+impl StoredBackup {
+    fn summary(&self) -> BufferBackup {
+        BufferBackup {
+            buffer_id: self.buffer_id.clone(),
+            path: self.path.clone(),
+            saved_at: self.saved_at,
+            size: self.content.len() as u64,
+            base_version: self.base_version.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecoverableBuffer {
+    pub backup: BufferBackup,
+    #[serde(rename = "diskState")]
+    pub disk_state: String,
+    #[serde(rename = "diskVersion")]
+    pub disk_version: Option<FileVersion>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RestoredBuffer {
+    pub backup: BufferBackup,
+    pub content: String,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn hash_key(key: &str) -> CommandResult<String> {
+    Ok(git2::Oid::hash_object(git2::ObjectType::Blob, key.as_bytes())?.to_string())
+}
+
+fn backup_dir(workspace: &str) -> CommandResult<PathBuf> {
+    let root = ensure_allowed(workspace)?;
+    let key = hash_key(&root.to_string_lossy())?;
+    Ok(litcode_dir()?.join("backups").join(key))
+}
+
+fn backup_file(workspace: &str, buffer_id: &str) -> CommandResult<PathBuf> {
+    Ok(backup_dir(workspace)?.join(format!("{}.json", hash_key(buffer_id)?)))
+}
+
+fn load_backup(file: &Path) -> CommandResult<StoredBackup> {
+    let json = fs::read_to_string(file).with_path(file)?;
+    serde_json::from_str(&json).map_err(|e| CommandError::other(e.to_string()).with_path(file))
+}
+
+fn disk_state(backup: &StoredBackup) -> CommandResult<(String, Option<FileVersion>)> {
+    let path = match &backup.path {
+        Some(path) => ensure_allowed(path)?,
+        None => return Ok(("untitled".to_string(), None)),
+    };
+    let bytes = match fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(("deleted".to_string(), None)),
+        Err(e) => return Err(CommandError::from(e).with_path(&path)),
+    };
+    let metadata = fs::metadata(&path).with_path(&path)?;
+    let version = FileVersion::from_bytes(&metadata, &bytes);
+
+    let state = if encoding::decode(&bytes, None).text == backup.content {
+        "identical"
+    } else if backup.base_version.as_ref().is_some_and(|base| !base.same_content(&version)) {
+        "changed"
+    } else {
+        "unchanged"
+    };
+    Ok((state.to_string(), Some(version)))
+}
+
+#[tauri::command]
+pub fn backup_buffer(
+    workspace: &str,
+    buffer_id: &str,
+    path: Option<String>,
+    content: String,
+    base_version: Option<FileVersion>,
+) -> CommandResult<BufferBackup> {
+    if let Some(path) = &path {
+        ensure_allowed(path)?;
+    }
+    let file = backup_file(workspace, buffer_id)?;
+    let backup = StoredBackup {
+        buffer_id: buffer_id.to_string(),
+        path,
+        saved_at: now_millis(),
+        base_version,
+        content,
+    };
+
+    let json = serde_json::to_vec(&backup).map_err(|e| CommandError::other(e.to_string()))?;
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).with_path(parent)?;
+    }
+    atomic_write(&file, &json)?;
+    Ok(backup.summary())
+}
+
+fn stored_backups(workspace: &str) -> CommandResult<Vec<(PathBuf, StoredBackup)>> {
+    let dir = backup_dir(workspace)?;
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(CommandError::from(e).with_path(&dir)),
+    };
+    Ok(entries
+        .flatten()
+        .filter_map(|entry| {
+            let file = entry.path();
+            load_backup(&file).ok().map(|backup| (file, backup))
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub fn backup_list(workspace: &str) -> CommandResult<Vec<RecoverableBuffer>> {
+    let mut buffers = Vec::new();
+    for (_, backup) in stored_backups(workspace)? {
+        let (state, disk_version) = match disk_state(&backup) {
+            Ok(state) => state,
+            Err(_) => continue,
+        };
+        if state == "identical" {
+            continue;
+        }
+        buffers.push(RecoverableBuffer {
+            backup: backup.summary(),
+            disk_state: state,
+            disk_version,
+        });
+    }
+    buffers.sort_by_key(|b| std::cmp::Reverse(b.backup.saved_at));
+    Ok(buffers)
+}
+
+#[tauri::command]
+pub fn backup_prune(workspace: &str) -> CommandResult<usize> {
+    let mut removed = 0;
+    for (file, backup) in stored_backups(workspace)? {
+        if disk_state(&backup).is_ok_and(|(state, _)| state == "identical") && fs::remove_file(&file).is_ok() {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+#[tauri::command]
+pub fn backup_restore(workspace: &str, buffer_id: &str) -> CommandResult<RestoredBuffer> {
+    let file = backup_file(workspace, buffer_id)?;
+    let backup = load_backup(&file).map_err(|e| match e.kind {
+        ErrorKind::NotFound => CommandError::not_found("Backup not found"),
+        _ => e,
+    })?;
+    Ok(RestoredBuffer {
+        backup: backup.summary(),
+        content: backup.content,
+    })
+}
+
+#[tauri::command]
+pub fn backup_discard(workspace: &str, buffer_id: &str) -> CommandResult<()> {
+    let file = backup_file(workspace, buffer_id)?;
+    match fs::remove_file(&file) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(CommandError::from(e).with_path(&file)),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::workspace::trust_root;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("litcode-backup-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        trust_root(&dir).unwrap()
+    }
+
+    fn backup(workspace: &str, path: &Path, content: &str) {
+        let path = path.to_string_lossy().to_string();
+        backup_buffer(workspace, &path, Some(path.clone()), content.to_string(), None).unwrap();
+    }
+
+    #[test]
+    fn list_reports_deleted_files_until_recreated() {
+        let dir = scratch_dir("deleted");
+        let workspace = dir.to_str().unwrap();
+        let file = dir.join("gone.txt");
+        fs::write(&file, "old").unwrap();
+        backup(workspace, &file, "unsaved");
+        fs::remove_file(&file).unwrap();
+
+        assert_eq!(backup_prune(workspace).unwrap(), 0);
+        let listed = backup_list(workspace).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].disk_state, "deleted");
+
+        fs::write(&file, "unsaved").unwrap();
+        assert!(backup_list(workspace).unwrap().is_empty());
+        assert_eq!(backup_prune(workspace).unwrap(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn list_distinguishes_changed_files() {
+        let dir = scratch_dir("changed");
+        let workspace = dir.to_str().unwrap();
+        let file = dir.join("a.txt");
+        fs::write(&file, "base").unwrap();
+        let base = load_file_version(&file);
+        let path = file.to_string_lossy().to_string();
+        backup_buffer(workspace, &path, Some(path.clone()), "edited".to_string(), Some(base)).unwrap();
+
+        assert_eq!(backup_list(workspace).unwrap()[0].disk_state, "unchanged");
+        fs::write(&file, "changed elsewhere").unwrap();
+        assert_eq!(backup_list(workspace).unwrap()[0].disk_state, "changed");
+
+        let restored = backup_restore(workspace, &path).unwrap();
+        assert_eq!(restored.content, "edited");
+        backup_discard(workspace, &path).unwrap();
+        assert!(backup_list(workspace).unwrap().is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn load_file_version(path: &Path) -> FileVersion {
+        let bytes = fs::read(path).unwrap();
+        FileVersion::from_bytes(&fs::metadata(path).unwrap(), &bytes)
+    }
+}
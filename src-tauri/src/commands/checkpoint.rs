@@ -0,0 +1,276 @@
+use super::error::{CommandError, CommandResult, ResultExt};
+use super::fs::{atomic_write, remove_path_permanently};
+use super::git::{diff_files, open_repo, GitDiff};
+use super::workspace::ensure_entry_allowed;
+use git2::{DiffOptions, FileMode, IndexAddOption, Oid, Repository};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const CHECKPOINT_REF_PREFIX: &str = "refs/litcode/checkpoints/";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitCheckpoint {
+    pub id: String,
+    #[serde(rename = "refName")]
+    pub ref_name: String,
+    #[serde(rename = "commitId")]
+    pub commit_id: String,
+    pub message: String,
+    pub time: i64,
+    pub head: Option<String>,
+}
+
+fn generate_checkpoint_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    format!("{}-{}", timestamp, count)
+}
+
+fn checkpoint_ref(checkpoint_id: &str) -> CommandResult<String> {
+    let ref_name = format!("{}{}", CHECKPOINT_REF_PREFIX, checkpoint_id);
+    if checkpoint_id.is_empty() || checkpoint_id.contains('/') || !git2::Reference::is_valid_name(&ref_name) {
+        return Err(CommandError::invalid_input("Invalid checkpoint id"));
+    }
+    Ok(ref_name)
+}
+
+fn to_checkpoint(commit: &git2::Commit, ref_name: &str) -> GitCheckpoint {
+    GitCheckpoint {
+        id: ref_name.trim_start_matches(CHECKPOINT_REF_PREFIX).to_string(),
+        ref_name: ref_name.to_string(),
+        commit_id: commit.id().to_string(),
+        message: commit.message().unwrap_or("").to_string(),
+        time: commit.time().seconds(),
+        head: commit.parent_id(0).ok().map(|id| id.to_string()),
+    }
+}
+
+fn find_checkpoint<'r>(repo: &'r Repository, checkpoint_id: &str) -> CommandResult<git2::Commit<'r>> {
+    let ref_name = checkpoint_ref(checkpoint_id)?;
+    let reference = repo
+        .find_reference(&ref_name)
+        .map_err(|_| CommandError::not_found("Checkpoint not found"))?;
+    Ok(reference.peel_to_commit()?)
+}
+
+// Stages the working tree into a throwaway copy of the index; the on-disk
+// index is never written, so the user's staging area is left untouched.
+fn snapshot_tree(repo_path: &str) -> CommandResult<Oid> {
+    let repo = open_repo(repo_path)?;
+    let mut index = repo.index()?;
+    index.add_all(["*"].iter(), IndexAddOption::DEFAULT, None)?;
+    index.update_all(["*"].iter(), None)?;
+    Ok(index.write_tree_to(&repo)?)
+}
+
+fn create_checkpoint(repo_path: &str, message: &str) -> CommandResult<GitCheckpoint> {
+    let tree_id = snapshot_tree(repo_path)?;
+    let repo = open_repo(repo_path)?;
+    let tree = repo.find_tree(tree_id)?;
+
+    let signature = repo
+        .signature()
+        .or_else(|_| git2::Signature::now("Litcode", "litcode@localhost"))?;
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+
+    let ref_name = checkpoint_ref(&generate_checkpoint_id())?;
+    let oid = repo.commit(Some(&ref_name), &signature, &signature, message, &tree, &parents)?;
+    let commit = repo.find_commit(oid)?;
+    Ok(to_checkpoint(&commit, &ref_name))
+}
+
+fn write_entry(repo: &Repository, target: &Path, entry: &git2::DiffFile) -> CommandResult<()> {
+    let blob = repo.find_blob(entry.id())?;
+    if fs::symlink_metadata(target).is_ok_and(|m| m.is_dir() || m.file_type().is_symlink()) {
+        remove_path_permanently(target).with_path(target)?;
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).with_path(parent)?;
+    }
+
+    #[cfg(unix)]
+    if entry.mode() == FileMode::Link {
+        let link = String::from_utf8_lossy(blob.content()).to_string();
+        return std::os::unix::fs::symlink(link, target).with_path(target);
+    }
+
+    atomic_write(target, blob.content())?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = if entry.mode() == FileMode::BlobExecutable { 0o755 } else { 0o644 };
+        fs::set_permissions(target, fs::Permissions::from_mode(mode)).with_path(target)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn git_checkpoint_create(repo_path: &str, message: Option<String>) -> CommandResult<GitCheckpoint> {
+    create_checkpoint(repo_path, message.as_deref().unwrap_or("Checkpoint"))
+}
+
+#[tauri::command]
+pub fn git_checkpoint_list(repo_path: &str) -> CommandResult<Vec<GitCheckpoint>> {
+    let repo = open_repo(repo_path)?;
+    let mut checkpoints = Vec::new();
+    for reference in repo.references_glob(&format!("{}*", CHECKPOINT_REF_PREFIX))? {
+        let reference = reference?;
+        let (Some(name), Ok(commit)) = (reference.name(), reference.peel_to_commit()) else {
+            continue;
+        };
+        checkpoints.push(to_checkpoint(&commit, name));
+    }
+    checkpoints.sort_by_key(|c| std::cmp::Reverse((c.time, c.id.clone())));
+    Ok(checkpoints)
+}
+
+#[tauri::command]
+pub fn git_checkpoint_diff(
+    repo_path: &str,
+    checkpoint_id: &str,
+    file_path: Option<String>,
+) -> CommandResult<Vec<GitDiff>> {
+    let current = snapshot_tree(repo_path)?;
+    let repo = open_repo(repo_path)?;
+    let checkpoint_tree = find_checkpoint(&repo, checkpoint_id)?.tree()?;
+    let current_tree = repo.find_tree(current)?;
+
+    let mut opts = DiffOptions::new();
+    if let Some(file_path) = &file_path {
+        opts.pathspec(file_path).disable_pathspec_match(true);
+    }
+    let diff = repo.diff_tree_to_tree(Some(&checkpoint_tree), Some(&current_tree), Some(&mut opts))?;
+    diff_files(&diff)
+}
+
+#[tauri::command]
+pub fn git_checkpoint_restore(
+    repo_path: &str,
+    checkpoint_id: &str,
+    paths: Option<Vec<String>>,
+) -> CommandResult<GitCheckpoint> {
+    let repo = open_repo(repo_path)?;
+    let checkpoint_tree = find_checkpoint(&repo, checkpoint_id)?.tree()?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| CommandError::invalid_input("Repository has no working directory"))?
+        .to_path_buf();
+
+    let backup = create_checkpoint(repo_path, &format!("Before restoring checkpoint {}", checkpoint_id))?;
+    let current_tree = repo.find_commit(Oid::from_str(&backup.commit_id)?)?.tree()?;
+
+    let mut opts = DiffOptions::new();
+    for path in paths.iter().flatten() {
+        opts.pathspec(path);
+    }
+    if paths.is_some() {
+        opts.disable_pathspec_match(true);
+    }
+    let diff = repo.diff_tree_to_tree(Some(&current_tree), Some(&checkpoint_tree), Some(&mut opts))?;
+
+    for delta in diff.deltas() {
+        let relative = match delta.new_file().path().or_else(|| delta.old_file().path()) {
+            Some(path) => path.to_path_buf(),
+            None => continue,
+        };
+        let target = ensure_entry_allowed(workdir.join(&relative))?;
+        if delta.status() == git2::Delta::Deleted {
+            remove_path_permanently(&target).with_path(&target)?;
+        } else {
+            write_entry(&repo, &target, &delta.new_file())?;
+        }
+    }
+
+    Ok(backup)
+}
+
+#[tauri::command]
+pub fn git_checkpoint_delete(repo_path: &str, checkpoint_id: &str) -> CommandResult<()> {
+    let repo = open_repo(repo_path)?;
+    let ref_name = checkpoint_ref(checkpoint_id)?;
+    let mut reference = repo
+        .find_reference(&ref_name)
+        .map_err(|_| CommandError::not_found("Checkpoint not found"))?;
+    reference.delete()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::workspace::trust_root;
+    use std::path::PathBuf;
+
+    fn scratch_repo(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("litcode-checkpoint-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let dir = trust_root(&dir).unwrap();
+        let repo = Repository::init(&dir).unwrap();
+        fs::write(dir.join("a.txt"), "one\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[]).unwrap();
+        dir
+    }
+
+    #[test]
+    fn checkpoint_round_trip_leaves_index_alone() {
+        let dir = scratch_repo("round-trip");
+        let repo_path = dir.to_str().unwrap();
+        fs::write(dir.join("a.txt"), "two\n").unwrap();
+        fs::write(dir.join("new.txt"), "new\n").unwrap();
+
+        let checkpoint = git_checkpoint_create(repo_path, Some("work".into())).unwrap();
+        let repo = Repository::open(&dir).unwrap();
+        assert!(repo.statuses(None).unwrap().iter().all(|s| !s.status().is_index_new()));
+        assert_eq!(git_checkpoint_list(repo_path).unwrap()[0].id, checkpoint.id);
+
+        fs::write(dir.join("a.txt"), "three\n").unwrap();
+        fs::remove_file(dir.join("new.txt")).unwrap();
+        fs::write(dir.join("later.txt"), "later\n").unwrap();
+        let diffs = git_checkpoint_diff(repo_path, &checkpoint.id, None).unwrap();
+        let mut paths: Vec<_> = diffs.iter().map(|d| d.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["a.txt", "later.txt", "new.txt"]);
+
+        let backup = git_checkpoint_restore(repo_path, &checkpoint.id, None).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("a.txt")).unwrap(), "two\n");
+        assert_eq!(fs::read_to_string(dir.join("new.txt")).unwrap(), "new\n");
+        assert!(!dir.join("later.txt").exists());
+        assert_eq!(git_checkpoint_list(repo_path).unwrap().len(), 2);
+
+        git_checkpoint_restore(repo_path, &backup.id, Some(vec!["later.txt".into()])).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("later.txt")).unwrap(), "later\n");
+        assert_eq!(fs::read_to_string(dir.join("a.txt")).unwrap(), "two\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn checkpoint_ids_are_validated() {
+        let dir = scratch_repo("ids");
+        let repo_path = dir.to_str().unwrap();
+        for id in ["", "../HEAD", "a/b", "bad..id"] {
+            assert!(git_checkpoint_delete(repo_path, id).is_err(), "{:?} should be rejected", id);
+        }
+        let checkpoint = git_checkpoint_create(repo_path, None).unwrap();
+        git_checkpoint_delete(repo_path, &checkpoint.id).unwrap();
+        assert!(git_checkpoint_list(repo_path).unwrap().is_empty());
+        assert!(git_checkpoint_delete(repo_path, &checkpoint.id).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
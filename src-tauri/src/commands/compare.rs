@@ -0,0 +1,377 @@
+use super::encoding;
+use super::error::{CommandError, CommandResult, ErrorKind, ResultExt};
+use super::file_view::is_binary;
+use super::git::{diff_buffers, GitDiff};
+use super::search::should_skip_path;
+use super::workspace::ensure_allowed;
+use git2::DiffOptions;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+const MAX_DIFF_SIZE: u64 = 16 * 1024 * 1024;
+const MAX_FOLDER_ENTRIES: usize = 10_000;
+const COMPARE_BUFFER_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CompareOptions {
+    #[serde(rename = "ignoreWhitespace", default)]
+    pub ignore_whitespace: bool,
+    #[serde(rename = "contextLines")]
+    pub context_lines: Option<u32>,
+    #[serde(rename = "applySkipRules", default)]
+    pub apply_skip_rules: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FolderDiffEntry {
+    pub path: String,
+    pub status: String,
+    #[serde(rename = "isDirectory")]
+    pub is_directory: bool,
+    #[serde(rename = "oldSize")]
+    pub old_size: Option<u64>,
+    #[serde(rename = "newSize")]
+    pub new_size: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FolderDiff {
+    #[serde(rename = "oldPath")]
+    pub old_path: String,
+    #[serde(rename = "newPath")]
+    pub new_path: String,
+    pub entries: Vec<FolderDiffEntry>,
+    pub truncated: bool,
+}
+
+#[derive(PartialEq, Eq)]
+enum Content {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+#[derive(Clone, PartialEq, Eq)]
+enum EntryKind {
+    File(u64),
+    Directory,
+    Symlink(PathBuf),
+}
+
+fn diff_options(options: &CompareOptions) -> DiffOptions {
+    let mut opts = DiffOptions::new();
+    if options.ignore_whitespace {
+        opts.ignore_whitespace(true);
+    }
+    if let Some(lines) = options.context_lines {
+        opts.context_lines(lines);
+    }
+    opts
+}
+
+fn read_content(path: &Path) -> CommandResult<Content> {
+    let metadata = fs::metadata(path).with_path(path)?;
+    if metadata.is_dir() {
+        return Err(CommandError::invalid_input("Use diff_folders to compare folders").with_path(path));
+    }
+    if metadata.len() > MAX_DIFF_SIZE {
+        return Err(CommandError::invalid_input("File is too large to compare").with_path(path));
+    }
+    let bytes = fs::read(path).with_path(path)?;
+    if is_binary(&bytes) {
+        return Ok(Content::Binary(bytes));
+    }
+    Ok(Content::Text(encoding::decode(&bytes, None).text))
+}
+
+fn diff_contents(old: Content, new: Content, label: &str, options: &CompareOptions) -> CommandResult<GitDiff> {
+    let hunks = match (old, new) {
+        (Content::Text(old), Content::Text(new)) => {
+            diff_buffers(old.as_bytes(), new.as_bytes(), label, Some(&mut diff_options(options)))?
+        }
+        (old, new) if old == new => Vec::new(),
+        _ => {
+            return Err(CommandError::new(ErrorKind::Unsupported, "Binary files differ").with_path(label));
+        }
+    };
+    Ok(GitDiff {
+        path: label.to_string(),
+        hunks,
+        lfs: None,
+    })
+}
+
+fn entry_kind(path: &Path) -> Option<EntryKind> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    Some(if metadata.file_type().is_symlink() {
+        EntryKind::Symlink(fs::read_link(path).ok()?)
+    } else if metadata.is_dir() {
+        EntryKind::Directory
+    } else {
+        EntryKind::File(metadata.len())
+    })
+}
+
+fn collect_entries(
+    root: &Path,
+    dir: &Path,
+    options: &CompareOptions,
+    entries: &mut BTreeMap<PathBuf, EntryKind>,
+) -> CommandResult<()> {
+    for entry in fs::read_dir(dir).with_path(dir)?.flatten() {
+        let path = entry.path();
+        if options.apply_skip_rules && should_skip_path(&path) {
+            continue;
+        }
+        let kind = match entry_kind(&path) {
+            Some(kind) => kind,
+            None => continue,
+        };
+        let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+        if kind == EntryKind::Directory {
+            collect_entries(root, &path, options, entries)?;
+        }
+        entries.insert(relative, kind);
+    }
+    Ok(())
+}
+
+fn same_content(old: &Path, new: &Path) -> CommandResult<bool> {
+    let mut old_reader = BufReader::new(File::open(old).with_path(old)?);
+    let mut new_reader = BufReader::new(File::open(new).with_path(new)?);
+    let mut old_buffer = vec![0u8; COMPARE_BUFFER_SIZE];
+    let mut new_buffer = vec![0u8; COMPARE_BUFFER_SIZE];
+    loop {
+        let read = old_reader.read(&mut old_buffer).with_path(old)?;
+        if read == 0 {
+            return Ok(new_reader.read(&mut new_buffer[..1]).with_path(new)? == 0);
+        }
+        if new_reader.read_exact(&mut new_buffer[..read]).is_err() || old_buffer[..read] != new_buffer[..read] {
+            return Ok(false);
+        }
+    }
+}
+
+fn size_of(kind: Option<&EntryKind>) -> Option<u64> {
+    match kind {
+        Some(EntryKind::File(size)) => Some(*size),
+        _ => None,
+    }
+}
+
+fn compare_folders(old_root: &Path, new_root: &Path, options: &CompareOptions) -> CommandResult<FolderDiff> {
+    let mut old_entries = BTreeMap::new();
+    let mut new_entries = BTreeMap::new();
+    collect_entries(old_root, old_root, options, &mut old_entries)?;
+    collect_entries(new_root, new_root, options, &mut new_entries)?;
+
+    let mut paths: Vec<&PathBuf> = old_entries.keys().chain(new_entries.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    let mut entries = Vec::new();
+    let mut truncated = false;
+    let mut reported_dirs: Vec<&PathBuf> = Vec::new();
+    for relative in paths {
+        if reported_dirs.iter().any(|dir| relative.starts_with(dir)) {
+            continue;
+        }
+        let old = old_entries.get(relative);
+        let new = new_entries.get(relative);
+        let status = match (old, new) {
+            (None, Some(_)) => "added",
+            (Some(_), None) => "removed",
+            (Some(EntryKind::Directory), Some(EntryKind::Directory)) => continue,
+            (Some(EntryKind::File(old_size)), Some(EntryKind::File(new_size))) => {
+                if old_size == new_size && same_content(&old_root.join(relative), &new_root.join(relative))? {
+                    continue;
+                }
+                "changed"
+            }
+            (Some(old), Some(new)) if old == new => continue,
+            _ => "changed",
+        };
+        let kind = new.or(old);
+        let is_directory = kind == Some(&EntryKind::Directory);
+        if is_directory && status != "changed" {
+            reported_dirs.push(relative);
+        }
+
+        if entries.len() >= MAX_FOLDER_ENTRIES {
+            truncated = true;
+            break;
+        }
+        entries.push(FolderDiffEntry {
+            path: relative.to_string_lossy().replace('\\', "/"),
+            status: status.to_string(),
+            is_directory,
+            old_size: size_of(old),
+            new_size: size_of(new),
+        });
+    }
+
+    Ok(FolderDiff {
+        old_path: old_root.to_string_lossy().to_string(),
+        new_path: new_root.to_string_lossy().to_string(),
+        entries,
+        truncated,
+    })
+}
+
+#[tauri::command]
+pub async fn diff_texts(
+    old_text: String,
+    new_text: String,
+    label: Option<String>,
+    options: Option<CompareOptions>,
+) -> CommandResult<GitDiff> {
+    tokio::task::spawn_blocking(move || {
+        let label = label.unwrap_or_else(|| "untitled".to_string());
+        diff_contents(Content::Text(old_text), Content::Text(new_text), &label, &options.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| CommandError::other(format!("Compare failed: {}", e)))?
+}
+
+#[tauri::command]
+pub async fn diff_paths(
+    old_path: String,
+    new_path: String,
+    options: Option<CompareOptions>,
+) -> CommandResult<GitDiff> {
+    tokio::task::spawn_blocking(move || {
+        let old_content = read_content(&ensure_allowed(&old_path)?)?;
+        let new_content = read_content(&ensure_allowed(&new_path)?)?;
+        diff_contents(old_content, new_content, &new_path, &options.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| CommandError::other(format!("Compare failed: {}", e)))?
+}
+
+#[tauri::command]
+pub async fn diff_path_with_text(
+    path: String,
+    text: String,
+    options: Option<CompareOptions>,
+) -> CommandResult<GitDiff> {
+    tokio::task::spawn_blocking(move || {
+        let old_content = read_content(&ensure_allowed(&path)?)?;
+        diff_contents(old_content, Content::Text(text), &path, &options.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| CommandError::other(format!("Compare failed: {}", e)))?
+}
+
+#[tauri::command]
+pub async fn diff_folders(
+    old_path: String,
+    new_path: String,
+    options: Option<CompareOptions>,
+) -> CommandResult<FolderDiff> {
+    tokio::task::spawn_blocking(move || {
+        let old_root = ensure_allowed(&old_path)?;
+        let new_root = ensure_allowed(&new_path)?;
+        for root in [&old_root, &new_root] {
+            if !root.is_dir() {
+                return Err(CommandError::invalid_input("Folder compare needs two directories").with_path(root));
+            }
+        }
+        compare_folders(&old_root, &new_root, &options.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| CommandError::other(format!("Folder compare failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("litcode-compare-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write(root: &Path, relative: &str, content: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn statuses(diff: &FolderDiff) -> Vec<(&str, &str)> {
+        diff.entries.iter().map(|e| (e.path.as_str(), e.status.as_str())).collect()
+    }
+
+    #[test]
+    fn compare_folders_reports_added_removed_and_changed() {
+        let dir = scratch_dir("basic");
+        let (old, new) = (dir.join("old"), dir.join("new"));
+        write(&old, "same.txt", "same");
+        write(&new, "same.txt", "same");
+        write(&old, "edited.txt", "abc");
+        write(&new, "edited.txt", "abd");
+        write(&old, "resized.txt", "a");
+        write(&new, "resized.txt", "ab");
+        write(&old, "gone.txt", "x");
+        write(&new, "fresh/nested/a.txt", "y");
+        write(&old, "shared/b.txt", "b");
+        write(&new, "shared/b.txt", "b");
+
+        let diff = compare_folders(&old, &new, &CompareOptions::default()).unwrap();
+
+        assert_eq!(
+            statuses(&diff),
+            vec![
+                ("edited.txt", "changed"),
+                ("fresh", "added"),
+                ("gone.txt", "removed"),
+                ("resized.txt", "changed"),
+            ]
+        );
+        let resized = diff.entries.iter().find(|e| e.path == "resized.txt").unwrap();
+        assert_eq!((resized.old_size, resized.new_size), (Some(1), Some(2)));
+        assert!(diff.entries.iter().find(|e| e.path == "fresh").unwrap().is_directory);
+        assert!(!diff.truncated);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn compare_folders_handles_kind_changes_and_skip_rules() {
+        let dir = scratch_dir("kinds");
+        let (old, new) = (dir.join("old"), dir.join("new"));
+        write(&old, "entry", "file");
+        write(&new, "entry/inner.txt", "now a folder");
+        write(&old, "node_modules/pkg/index.js", "a");
+        write(&new, "node_modules/pkg/index.js", "b");
+
+        let diff = compare_folders(&old, &new, &CompareOptions::default()).unwrap();
+        assert_eq!(
+            statuses(&diff),
+            vec![("entry", "changed"), ("entry/inner.txt", "added"), ("node_modules/pkg/index.js", "changed")]
+        );
+
+        let options = CompareOptions {
+            apply_skip_rules: true,
+            ..Default::default()
+        };
+        let diff = compare_folders(&old, &new, &options).unwrap();
+        assert_eq!(statuses(&diff), vec![("entry", "changed"), ("entry/inner.txt", "added")]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn same_content_compares_bytes() {
+        let dir = scratch_dir("bytes");
+        let large = "x".repeat(COMPARE_BUFFER_SIZE + 10);
+        write(&dir, "a", &large);
+        write(&dir, "b", &large);
+        write(&dir, "c", &format!("{}y", &large[..large.len() - 1]));
+
+        assert!(same_content(&dir.join("a"), &dir.join("b")).unwrap());
+        assert!(!same_content(&dir.join("a"), &dir.join("c")).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
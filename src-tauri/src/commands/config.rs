@@ -1,33 +1,40 @@
+use super::error::{CommandError, CommandResult, ResultExt};
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-fn get_config_path() -> Result<String, String> {
-    let home = env::var("HOME").map_err(|_| "Could not determine home directory")?;
-    Ok(format!("{}/.litcode/config.json", home))
+pub(crate) fn litcode_dir() -> CommandResult<PathBuf> {
+    if cfg!(test) {
+        return Ok(env::temp_dir().join(format!("litcode-home-{}", std::process::id())));
+    }
+    let home = env::var("HOME").map_err(|_| CommandError::not_found("Could not determine home directory"))?;
+    Ok(Path::new(&home).join(".litcode"))
+}
+
+fn get_config_path() -> CommandResult<PathBuf> {
+    Ok(litcode_dir()?.join("config.json"))
 }
 
-fn ensure_config_dir() -> Result<(), String> {
-    let home = env::var("HOME").map_err(|_| "Could not determine home directory")?;
-    let dir = format!("{}/.litcode", home);
-    if !Path::new(&dir).exists() {
-        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create config directory: {}", e))?;
+fn ensure_config_dir() -> CommandResult<()> {
+    let dir = litcode_dir()?;
+    if !dir.exists() {
+        fs::create_dir_all(&dir).with_path(&dir)?;
     }
     Ok(())
 }
 
 #[tauri::command]
-pub fn config_read() -> Result<String, String> {
+pub fn config_read() -> CommandResult<String> {
     let path = get_config_path()?;
-    if !Path::new(&path).exists() {
+    if !path.exists() {
         return Ok("{}".to_string());
     }
-    fs::read_to_string(&path).map_err(|e| e.to_string())
+    fs::read_to_string(&path).with_path(&path)
 }
 
 #[tauri::command]
-pub fn config_write(config: &str) -> Result<(), String> {
+pub fn config_write(config: &str) -> CommandResult<()> {
     ensure_config_dir()?;
     let path = get_config_path()?;
-    fs::write(&path, config).map_err(|e| e.to_string())
+    fs::write(&path, config).with_path(&path)
 }
@@ -0,0 +1,326 @@
+use super::error::{CommandError, CommandResult, ResultExt};
+use super::search::should_skip_path;
+use super::transfer::generate_operation_id;
+use super::workspace::ensure_allowed;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+const DEFAULT_LARGEST_CHILDREN: usize = 20;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DiskUsageOptions {
+    #[serde(rename = "applySkipRules", default)]
+    pub apply_skip_rules: bool,
+    #[serde(rename = "maxChildren")]
+    pub max_children: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiskUsageEntry {
+    pub path: String,
+    pub name: String,
+    #[serde(rename = "isDirectory")]
+    pub is_directory: bool,
+    pub size: u64,
+    #[serde(rename = "fileCount")]
+    pub file_count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiskUsage {
+    #[serde(rename = "operationId")]
+    pub operation_id: String,
+    pub path: String,
+    pub size: u64,
+    #[serde(rename = "fileCount")]
+    pub file_count: u64,
+    #[serde(rename = "dirCount")]
+    pub dir_count: u64,
+    pub children: Vec<DiskUsageEntry>,
+    #[serde(rename = "moreChildren")]
+    pub more_children: usize,
+    pub errors: u64,
+    pub cancelled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiskUsageProgress {
+    #[serde(rename = "operationId")]
+    pub operation_id: String,
+    #[serde(rename = "currentPath")]
+    pub current_path: Option<String>,
+    pub size: u64,
+    #[serde(rename = "fileCount")]
+    pub file_count: u64,
+    #[serde(rename = "dirCount")]
+    pub dir_count: u64,
+    pub done: bool,
+}
+
+static JOBS: Mutex<Option<HashMap<String, Arc<AtomicBool>>>> = Mutex::new(None);
+
+struct Scan {
+    emit: Box<dyn Fn(DiskUsageProgress) + Send + Sync>,
+    operation_id: String,
+    cancelled: Arc<AtomicBool>,
+    apply_skip_rules: bool,
+    size: AtomicU64,
+    files: AtomicU64,
+    dirs: AtomicU64,
+    errors: AtomicU64,
+    last_emit: Mutex<Instant>,
+}
+
+impl Scan {
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    fn progress(&self, current_path: Option<&Path>, done: bool) -> DiskUsageProgress {
+        DiskUsageProgress {
+            operation_id: self.operation_id.clone(),
+            current_path: current_path.map(|p| p.to_string_lossy().to_string()),
+            size: self.size.load(Ordering::Relaxed),
+            file_count: self.files.load(Ordering::Relaxed),
+            dir_count: self.dirs.load(Ordering::Relaxed),
+            done,
+        }
+    }
+
+    fn report(&self, path: &Path) {
+        let mut last_emit = match self.last_emit.try_lock() {
+            Ok(last_emit) => last_emit,
+            Err(_) => return,
+        };
+        if last_emit.elapsed() >= PROGRESS_INTERVAL {
+            *last_emit = Instant::now();
+            (self.emit)(self.progress(Some(path), false));
+        }
+    }
+
+    fn finish(&self) {
+        (self.emit)(self.progress(None, true));
+    }
+
+    fn walk(&self, dir: &Path) -> (u64, u64) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+                return (0, 0);
+            }
+        };
+        self.report(dir);
+
+        let mut totals = (0, 0);
+        for entry in entries.flatten() {
+            if self.is_cancelled() {
+                break;
+            }
+            let path = entry.path();
+            if self.is_skipped(&path) {
+                continue;
+            }
+            let (size, files) = self.measure(&path);
+            totals.0 += size;
+            totals.1 += files;
+        }
+        totals
+    }
+
+    fn measure(&self, path: &Path) -> (u64, u64) {
+        let metadata = match fs::symlink_metadata(path) {
+            Ok(metadata) => metadata,
+            Err(_) => {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+                return (0, 0);
+            }
+        };
+        if metadata.is_dir() {
+            self.dirs.fetch_add(1, Ordering::Relaxed);
+            return self.walk(path);
+        }
+        self.size.fetch_add(metadata.len(), Ordering::Relaxed);
+        self.files.fetch_add(1, Ordering::Relaxed);
+        (metadata.len(), 1)
+    }
+
+    fn is_skipped(&self, path: &Path) -> bool {
+        self.apply_skip_rules && should_skip_path(path)
+    }
+}
+
+fn register_job(operation_id: &str) -> Arc<AtomicBool> {
+    let flag = Arc::new(AtomicBool::new(false));
+    JOBS.lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(operation_id.to_string(), flag.clone());
+    flag
+}
+
+fn finish_job(operation_id: &str) {
+    if let Some(jobs) = JOBS.lock().unwrap().as_mut() {
+        jobs.remove(operation_id);
+    }
+}
+
+fn analyze(scan: &Scan, root: &Path, max_children: usize) -> CommandResult<DiskUsage> {
+    let metadata = fs::symlink_metadata(root).with_path(root)?;
+    let mut children: Vec<DiskUsageEntry> = Vec::new();
+    if metadata.is_dir() {
+        let paths: Vec<_> = fs::read_dir(root)
+            .with_path(root)?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| !scan.is_skipped(p))
+            .collect();
+        children = paths
+            .par_iter()
+            .map(|path| {
+                let is_directory = fs::symlink_metadata(path).is_ok_and(|m| m.is_dir());
+                let (size, file_count) = scan.measure(path);
+                DiskUsageEntry {
+                    path: path.to_string_lossy().to_string(),
+                    name: path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    is_directory,
+                    size,
+                    file_count,
+                }
+            })
+            .collect();
+    } else {
+        scan.measure(root);
+    }
+
+    children.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+    let more_children = children.len().saturating_sub(max_children);
+    children.truncate(max_children);
+
+    Ok(DiskUsage {
+        operation_id: scan.operation_id.clone(),
+        path: root.to_string_lossy().to_string(),
+        size: scan.size.load(Ordering::Relaxed),
+        file_count: scan.files.load(Ordering::Relaxed),
+        dir_count: scan.dirs.load(Ordering::Relaxed),
+        children,
+        more_children,
+        errors: scan.errors.load(Ordering::Relaxed),
+        cancelled: scan.is_cancelled(),
+    })
+}
+
+#[tauri::command]
+pub async fn disk_usage(
+    app: AppHandle,
+    path: String,
+    options: Option<DiskUsageOptions>,
+    operation_id: Option<String>,
+) -> CommandResult<DiskUsage> {
+    let operation_id = operation_id.unwrap_or_else(|| generate_operation_id("disk-usage"));
+    let options = options.unwrap_or_default();
+    let cancelled = register_job(&operation_id);
+    let job_id = operation_id.clone();
+
+    let result = tokio::task::spawn_blocking(move || {
+        let root = ensure_allowed(&path)?;
+        let scan = Scan {
+            emit: Box::new(move |progress| {
+                let _ = app.emit("fs:disk-usage-progress", progress);
+            }),
+            operation_id,
+            cancelled,
+            apply_skip_rules: options.apply_skip_rules,
+            size: AtomicU64::new(0),
+            files: AtomicU64::new(0),
+            dirs: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            last_emit: Mutex::new(Instant::now()),
+        };
+        let result = analyze(&scan, &root, options.max_children.unwrap_or(DEFAULT_LARGEST_CHILDREN));
+        scan.finish();
+        result
+    })
+    .await
+    .map_err(|e| CommandError::other(format!("Disk usage analysis failed: {}", e)));
+
+    finish_job(&job_id);
+    result?
+}
+
+#[tauri::command]
+pub fn disk_usage_cancel(operation_id: &str) -> bool {
+    match JOBS.lock().unwrap().as_ref().and_then(|jobs| jobs.get(operation_id)) {
+        Some(flag) => {
+            flag.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(apply_skip_rules: bool) -> Scan {
+        Scan {
+            emit: Box::new(|_| {}),
+            operation_id: "test".to_string(),
+            cancelled: Arc::new(AtomicBool::new(false)),
+            apply_skip_rules,
+            size: AtomicU64::new(0),
+            files: AtomicU64::new(0),
+            dirs: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            last_emit: Mutex::new(Instant::now()),
+        }
+    }
+
+    #[test]
+    fn analyze_totals_children_largest_first() {
+        let dir = std::env::temp_dir().join(format!("litcode-disk-usage-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("big/nested")).unwrap();
+        fs::create_dir_all(dir.join("node_modules")).unwrap();
+        fs::write(dir.join("big/a.dat"), vec![0u8; 300]).unwrap();
+        fs::write(dir.join("big/nested/b.dat"), vec![0u8; 200]).unwrap();
+        fs::write(dir.join("small.txt"), vec![0u8; 10]).unwrap();
+        fs::write(dir.join("tiny.txt"), vec![0u8; 1]).unwrap();
+        fs::write(dir.join("node_modules/dep.js"), vec![0u8; 1000]).unwrap();
+
+        let usage = analyze(&scan(true), &dir, 2).unwrap();
+        assert_eq!((usage.size, usage.file_count, usage.dir_count), (511, 4, 2));
+        let children: Vec<_> = usage.children.iter().map(|c| (c.name.as_str(), c.size, c.is_directory)).collect();
+        assert_eq!(children, vec![("big", 500, true), ("small.txt", 10, false)]);
+        assert_eq!(usage.more_children, 1);
+
+        let usage = analyze(&scan(false), &dir, 20).unwrap();
+        assert_eq!((usage.size, usage.children[0].name.as_str()), (1511, "node_modules"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn cancel_only_reaches_registered_jobs() {
+        let operation_id = generate_operation_id("disk-usage");
+        assert!(!disk_usage_cancel(&operation_id));
+
+        let flag = register_job(&operation_id);
+        assert!(disk_usage_cancel(&operation_id));
+        assert!(flag.load(Ordering::Relaxed));
+
+        finish_job(&operation_id);
+        assert!(!disk_usage_cancel(&operation_id));
+    }
+}
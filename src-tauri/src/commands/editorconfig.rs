@@ -0,0 +1,500 @@
+use super::error::CommandResult;
+use super::workspace::ensure_allowed;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CONFIG_FILE: &str = ".editorconfig";
+const KNOWN_PROPERTIES: &[&str] = &[
+    "indent_style",
+    "indent_size",
+    "tab_width",
+    "end_of_line",
+    "charset",
+    "trim_trailing_whitespace",
+    "insert_final_newline",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct EditorConfig {
+    #[serde(rename = "indentStyle")]
+    pub indent_style: Option<String>,
+    #[serde(rename = "indentSize")]
+    pub indent_size: Option<u32>,
+    #[serde(rename = "tabWidth")]
+    pub tab_width: Option<u32>,
+    #[serde(rename = "endOfLine")]
+    pub end_of_line: Option<String>,
+    pub charset: Option<String>,
+    #[serde(rename = "trimTrailingWhitespace")]
+    pub trim_trailing_whitespace: Option<bool>,
+    #[serde(rename = "insertFinalNewline")]
+    pub insert_final_newline: Option<bool>,
+    pub properties: BTreeMap<String, String>,
+}
+
+struct Section {
+    name: String,
+    properties: Vec<(String, String)>,
+}
+
+struct ConfigFile {
+    dir: PathBuf,
+    root: bool,
+    sections: Vec<Section>,
+}
+
+struct GlobMatcher {
+    regex: Regex,
+    ranges: Vec<(i64, i64)>,
+}
+
+impl GlobMatcher {
+    fn new(section: &str) -> Option<Self> {
+        let (anchored, glob) = match section.strip_prefix('/') {
+            Some(rest) => (true, rest),
+            None => (section.contains('/'), section),
+        };
+        let mut ranges = Vec::new();
+        let chars: Vec<char> = glob.chars().collect();
+        let body = translate(&chars, &mut ranges);
+        let pattern = if anchored {
+            format!("^{}$", body)
+        } else {
+            format!("^(?:.*/)?{}$", body)
+        };
+        Regex::new(&pattern).ok().map(|regex| GlobMatcher { regex, ranges })
+    }
+
+    fn is_match(&self, path: &str) -> bool {
+        let captures = match self.regex.captures(path) {
+            Some(captures) => captures,
+            None => return false,
+        };
+        self.ranges.iter().enumerate().all(|(i, (low, high))| {
+            captures.get(i + 1).is_none_or(|m| {
+                m.as_str()
+                    .parse::<i64>()
+                    .is_ok_and(|n| n >= *low && n <= *high)
+            })
+        })
+    }
+}
+
+fn find_closing(chars: &[char], open: usize, open_char: char, close_char: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = open;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            c if c == open_char => depth += 1,
+            c if c == close_char => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+fn split_alternatives(chars: &[char]) -> Vec<&[char]> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&chars[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    parts.push(&chars[start..]);
+    parts
+}
+
+fn numeric_range(chars: &[char]) -> Option<(i64, i64)> {
+    let inner: String = chars.iter().collect();
+    let (low, high) = inner.split_once("..")?;
+    Some((low.parse().ok()?, high.parse().ok()?))
+}
+
+fn translate(chars: &[char], ranges: &mut Vec<(i64, i64)>) -> String {
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                i += 1;
+                out.push_str(&regex::escape(&chars[i].to_string()));
+            }
+            '*' if i == 0 && chars.starts_with(&['*', '*', '/']) => {
+                out.push_str("(?:.*/)?");
+                i += 2;
+            }
+            '/' if chars[i..].starts_with(&['/', '*', '*', '/']) => {
+                out.push_str("(?:/|/.*/)");
+                i += 3;
+            }
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                out.push_str(".*");
+                i += 1;
+            }
+            '*' => out.push_str("[^/]*"),
+            '?' => out.push_str("[^/]"),
+            '[' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|c| *c == ']' || *c == '/')
+                    .map(|offset| i + 1 + offset)
+                    .filter(|end| chars[*end] == ']');
+                match end {
+                    Some(end) => {
+                        let mut class = &chars[i + 1..end];
+                        out.push('[');
+                        if let Some((first, rest)) = class.split_first() {
+                            if *first == '!' || *first == '^' {
+                                out.push('^');
+                                class = rest;
+                            }
+                        }
+                        for c in class {
+                            if matches!(c, '\\' | '[' | ']' | '^' | '&' | '~') {
+                                out.push('\\');
+                            }
+                            out.push(*c);
+                        }
+                        out.push(']');
+                        i = end;
+                    }
+                    None => out.push_str("\\["),
+                }
+            }
+            '{' => match find_closing(chars, i, '{', '}') {
+                Some(end) => {
+                    let inner = &chars[i + 1..end];
+                    if let Some(range) = numeric_range(inner) {
+                        ranges.push(range);
+                        out.push_str("([+-]?\\d+)");
+                    } else {
+                        let alternatives = split_alternatives(inner);
+                        if alternatives.len() > 1 {
+                            let translated: Vec<String> =
+                                alternatives.iter().map(|alt| translate(alt, ranges)).collect();
+                            out.push_str(&format!("(?:{})", translated.join("|")));
+                        } else {
+                            out.push_str(&format!("\\{{{}\\}}", translate(inner, ranges)));
+                        }
+                    }
+                    i = end;
+                }
+                None => out.push_str("\\{"),
+            },
+            c => out.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    out
+}
+
+fn parse(dir: &Path, content: &str) -> ConfigFile {
+    let mut config = ConfigFile {
+        dir: dir.to_path_buf(),
+        root: false,
+        sections: Vec::new(),
+    };
+
+    for line in content.trim_start_matches('\u{feff}').lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            config.sections.push(Section {
+                name: line[1..line.len() - 1].to_string(),
+                properties: Vec::new(),
+            });
+            continue;
+        }
+        let (key, value) = match line.split_once('=') {
+            Some((key, value)) => (key.trim().to_lowercase(), value.trim().to_string()),
+            None => continue,
+        };
+        match config.sections.last_mut() {
+            Some(section) => section.properties.push((key, value)),
+            None if key == "root" => config.root = value.eq_ignore_ascii_case("true"),
+            None => {}
+        }
+    }
+    config
+}
+
+fn config_files(path: &Path) -> Vec<ConfigFile> {
+    let mut files = Vec::new();
+    let mut dir = path.parent();
+    while let Some(current) = dir {
+        if let Ok(content) = fs::read_to_string(current.join(CONFIG_FILE)) {
+            let config = parse(current, &content);
+            let root = config.root;
+            files.push(config);
+            if root {
+                break;
+            }
+        }
+        dir = current.parent();
+    }
+    files.reverse();
+    files
+}
+
+fn relative_glob_path(path: &Path, dir: &Path) -> Option<String> {
+    let relative = path.strip_prefix(dir).ok()?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    Some(parts.join("/"))
+}
+
+fn parse_number(properties: &BTreeMap<String, String>, key: &str) -> Option<u32> {
+    properties.get(key).and_then(|v| v.parse().ok())
+}
+
+fn parse_bool(properties: &BTreeMap<String, String>, key: &str) -> Option<bool> {
+    match properties.get(key).map(String::as_str) {
+        Some("true") => Some(true),
+        Some("false") => Some(false),
+        _ => None,
+    }
+}
+
+pub(crate) fn resolve(path: &Path) -> EditorConfig {
+    let mut properties = BTreeMap::new();
+    for config in config_files(path) {
+        let relative = match relative_glob_path(path, &config.dir) {
+            Some(relative) => relative,
+            None => continue,
+        };
+        for section in &config.sections {
+            if !GlobMatcher::new(&section.name).is_some_and(|m| m.is_match(&relative)) {
+                continue;
+            }
+            for (key, value) in &section.properties {
+                let value = if KNOWN_PROPERTIES.contains(&key.as_str()) {
+                    value.to_lowercase()
+                } else {
+                    value.clone()
+                };
+                properties.insert(key.clone(), value);
+            }
+        }
+    }
+    properties.retain(|_, value| value != "unset");
+
+    if properties.get("indent_style").is_some_and(|v| v == "tab") && !properties.contains_key("indent_size") {
+        properties.insert("indent_size".to_string(), "tab".to_string());
+    }
+    if properties.get("indent_size").is_some_and(|v| v == "tab") {
+        if let Some(tab_width) = properties.get("tab_width").cloned() {
+            properties.insert("indent_size".to_string(), tab_width);
+        }
+    }
+    if !properties.contains_key("tab_width") {
+        if let Some(size) = properties.get("indent_size").filter(|v| v.parse::<u32>().is_ok()).cloned() {
+            properties.insert("tab_width".to_string(), size);
+        }
+    }
+
+    EditorConfig {
+        indent_style: properties.get("indent_style").cloned(),
+        indent_size: parse_number(&properties, "indent_size"),
+        tab_width: parse_number(&properties, "tab_width"),
+        end_of_line: properties.get("end_of_line").cloned(),
+        charset: properties.get("charset").cloned(),
+        trim_trailing_whitespace: parse_bool(&properties, "trim_trailing_whitespace"),
+        insert_final_newline: parse_bool(&properties, "insert_final_newline"),
+        properties,
+    }
+}
+
+fn split_lines(content: &str) -> Vec<(&str, &str)> {
+    let mut lines = Vec::new();
+    let bytes = content.as_bytes();
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let ending = match bytes[i] {
+            b'\r' if bytes.get(i + 1) == Some(&b'\n') => 2,
+            b'\r' | b'\n' => 1,
+            _ => 0,
+        };
+        if ending > 0 {
+            lines.push((&content[start..i], &content[i..i + ending]));
+            i += ending;
+            start = i;
+        } else {
+            i += 1;
+        }
+    }
+    if start < content.len() {
+        lines.push((&content[start..], ""));
+    }
+    lines
+}
+
+pub(crate) fn apply_whitespace_rules(content: &str, config: &EditorConfig) -> String {
+    let lines = split_lines(content);
+    let eol = match config.end_of_line.as_deref() {
+        Some("crlf") => Some("\r\n"),
+        Some("cr") => Some("\r"),
+        Some("lf") => Some("\n"),
+        _ => None,
+    };
+    let trim = config.trim_trailing_whitespace == Some(true);
+
+    let mut out = String::with_capacity(content.len());
+    for (line, ending) in &lines {
+        out.push_str(if trim { line.trim_end_matches([' ', '\t']) } else { line });
+        out.push_str(match eol {
+            Some(eol) if !ending.is_empty() => eol,
+            _ => ending,
+        });
+    }
+
+    let missing_newline = lines.last().is_some_and(|(_, ending)| ending.is_empty());
+    if config.insert_final_newline == Some(true) && missing_newline {
+        let fallback = lines.first().map(|(_, ending)| *ending).filter(|e| !e.is_empty());
+        out.push_str(eol.or(fallback).unwrap_or("\n"));
+    }
+    out
+}
+
+#[tauri::command]
+pub fn editorconfig_resolve(path: &str) -> CommandResult<EditorConfig> {
+    let resolved = ensure_allowed(path)?;
+    Ok(resolve(&resolved))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(section: &str, path: &str) -> bool {
+        GlobMatcher::new(section).is_some_and(|m| m.is_match(path))
+    }
+
+    #[test]
+    fn star_stays_within_one_component() {
+        assert!(matches("a*e.c", "ace.c"));
+        assert!(matches("a*e.c", "abcde.c"));
+        assert!(!matches("a*e.c", "a/e.c"));
+        assert!(matches("*.c", "src/main.c"));
+    }
+
+    #[test]
+    fn double_star_crosses_separators() {
+        assert!(matches("a**z.c", "az.c"));
+        assert!(matches("a**z.c", "amnz.c"));
+        assert!(matches("a**z.c", "am/nz.c"));
+        assert!(matches("a/**/z.c", "a/z.c"));
+        assert!(matches("a/**/z.c", "a/b/c/z.c"));
+        assert!(!matches("a/**/z.c", "az.c"));
+    }
+
+    #[test]
+    fn leading_double_star_matches_zero_directories() {
+        assert!(matches("**/z.c", "z.c"));
+        assert!(matches("**/z.c", "a/b/z.c"));
+        assert!(!matches("**/z.c", "az.c"));
+    }
+
+    #[test]
+    fn question_mark_matches_one_character() {
+        assert!(matches("som?.c", "some.c"));
+        assert!(!matches("som?.c", "som.c"));
+        assert!(!matches("a?c", "a/c"));
+    }
+
+    #[test]
+    fn brackets_match_character_classes() {
+        assert!(matches("[ab].a", "a.a"));
+        assert!(!matches("[ab].a", "c.a"));
+        assert!(matches("[!ab].b", "c.b"));
+        assert!(!matches("[!ab].b", "a.b"));
+        assert!(matches("[d-g].c", "f.c"));
+        assert!(!matches("[d-g].c", "h.c"));
+        assert!(matches("ab[e/]cd.i", "ab[e/]cd.i"));
+        assert!(!matches("ab[e/]cd.i", "ab/cd.i"));
+    }
+
+    #[test]
+    fn braces_match_alternatives() {
+        assert!(matches("*.{py,js}", "a.py"));
+        assert!(matches("*.{py,js}", "a.js"));
+        assert!(!matches("*.{py,js}", "a.rs"));
+        assert!(matches("{single}.b", "{single}.b"));
+        assert!(!matches("{single}.b", "single.b"));
+        assert!(matches("{}.c", "{}.c"));
+        assert!(matches("{.c", "{.c"));
+        assert!(matches("{,a}.d", ".d"));
+        assert!(matches("{,a}.d", "a.d"));
+    }
+
+    #[test]
+    fn nested_braces_expand_recursively() {
+        assert!(matches("{a,{b,c}}.x", "a.x"));
+        assert!(matches("{a,{b,c}}.x", "c.x"));
+        assert!(matches("{word,{also},this}.g", "word.g"));
+        assert!(matches("{word,{also},this}.g", "{also}.g"));
+        assert!(matches("{word,{also},this}.g", "this.g"));
+        assert!(!matches("{word,{also},this}.g", "also.g"));
+    }
+
+    #[test]
+    fn numeric_ranges_check_bounds() {
+        assert!(matches("{3..120}", "3"));
+        assert!(matches("{3..120}", "120"));
+        assert!(!matches("{3..120}", "121"));
+        assert!(!matches("{3..120}", "2"));
+        assert!(!matches("{3..120}", "a"));
+    }
+
+    #[test]
+    fn numeric_ranges_accept_negative_bounds() {
+        assert!(matches("f{-3..3}.txt", "f-3.txt"));
+        assert!(matches("f{-3..3}.txt", "f0.txt"));
+        assert!(matches("f{-3..3}.txt", "f3.txt"));
+        assert!(!matches("f{-3..3}.txt", "f-4.txt"));
+        assert!(!matches("f{-3..3}.txt", "f4.txt"));
+        assert!(matches("f{-5..-2}.txt", "f-2.txt"));
+        assert!(!matches("f{-5..-2}.txt", "f-1.txt"));
+    }
+
+    #[test]
+    fn backslash_escapes_special_characters() {
+        assert!(matches("\\*.c", "*.c"));
+        assert!(!matches("\\*.c", "a.c"));
+        assert!(matches("\\{a,b}", "{a,b}"));
+        assert!(matches("\\[ab].c", "[ab].c"));
+    }
+
+    #[test]
+    fn slash_anchors_to_config_directory() {
+        assert!(matches("src/*.rs", "src/lib.rs"));
+        assert!(!matches("src/*.rs", "crate/src/lib.rs"));
+        assert!(matches("/lib.rs", "lib.rs"));
+        assert!(!matches("/lib.rs", "src/lib.rs"));
+    }
+}
@@ -0,0 +1,208 @@
+use super::error::{CommandError, CommandResult};
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+
+const SNIFF_LIMIT: usize = 64 * 1024;
+
+pub struct Decoded {
+    pub text: String,
+    pub encoding: &'static Encoding,
+    pub bom: bool,
+    pub had_errors: bool,
+}
+
+pub fn lookup(label: &str) -> CommandResult<&'static Encoding> {
+    Encoding::for_label(label.trim().as_bytes())
+        .ok_or_else(|| CommandError::invalid_input(format!("Unknown encoding: {}", label)))
+}
+
+fn looks_like_utf16(bytes: &[u8]) -> Option<&'static Encoding> {
+    let sample = &bytes[..bytes.len().min(SNIFF_LIMIT)];
+    if sample.len() < 4 {
+        return None;
+    }
+    let pairs = sample.len() / 2;
+    let even_nuls = sample.iter().step_by(2).filter(|b| **b == 0).count();
+    let odd_nuls = sample.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+    if odd_nuls * 10 >= pairs * 4 && even_nuls * 10 < pairs {
+        Some(UTF_16LE)
+    } else if even_nuls * 10 >= pairs * 4 && odd_nuls * 10 < pairs {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+pub fn detect(bytes: &[u8]) -> (&'static Encoding, bool) {
+    detect_head(bytes, true)
+}
+
+pub fn detect_head(head: &[u8], complete: bool) -> (&'static Encoding, bool) {
+    if let Some((encoding, _)) = Encoding::for_bom(head) {
+        return (encoding, true);
+    }
+    if let Some(encoding) = looks_like_utf16(head) {
+        return (encoding, false);
+    }
+    match std::str::from_utf8(head) {
+        Ok(_) => return (UTF_8, false),
+        Err(e) if !complete && e.error_len().is_none() => return (UTF_8, false),
+        Err(_) => {}
+    }
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(&head[..head.len().min(SNIFF_LIMIT)], complete && head.len() <= SNIFF_LIMIT);
+    (detector.guess(None, true), false)
+}
+
+pub fn decode(bytes: &[u8], forced: Option<&'static Encoding>) -> Decoded {
+    let (encoding, bom) = match forced {
+        Some(encoding) => {
+            let bom = Encoding::for_bom(bytes).is_some_and(|(e, _)| e == encoding);
+            (encoding, bom)
+        }
+        None => detect(bytes),
+    };
+    let bom_len = if bom {
+        Encoding::for_bom(bytes).map(|(_, len)| len).unwrap_or(0)
+    } else {
+        0
+    };
+    let (text, had_errors) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+    Decoded {
+        text: text.into_owned(),
+        encoding,
+        bom,
+        had_errors,
+    }
+}
+
+pub fn encode(text: &str, encoding: &'static Encoding, bom: bool) -> CommandResult<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len() + 3);
+
+    if encoding == UTF_16LE || encoding == UTF_16BE {
+        if bom {
+            bytes.extend_from_slice(if encoding == UTF_16LE { &[0xFF, 0xFE] } else { &[0xFE, 0xFF] });
+        }
+        for unit in text.encode_utf16() {
+            let pair = if encoding == UTF_16LE {
+                unit.to_le_bytes()
+            } else {
+                unit.to_be_bytes()
+            };
+            bytes.extend_from_slice(&pair);
+        }
+        return Ok(bytes);
+    }
+
+    if encoding == UTF_8 {
+        if bom {
+            bytes.extend_from_slice(&[0xEF, 0xBB, 0xBF]);
+        }
+        bytes.extend_from_slice(text.as_bytes());
+        return Ok(bytes);
+    }
+
+    let (encoded, _, had_errors) = encoding.encode(text);
+    if had_errors {
+        return Err(CommandError::invalid_input(format!(
+            "Content contains characters that cannot be saved as {}",
+            encoding.name()
+        )));
+    }
+    bytes.extend_from_slice(&encoded);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding_rs::{SHIFT_JIS, WINDOWS_1252};
+
+    #[test]
+    fn detect_prefers_bom() {
+        assert_eq!(detect(&[0xEF, 0xBB, 0xBF, b'a']), (UTF_8, true));
+        assert_eq!(detect(&[0xFF, 0xFE, b'a', 0]), (UTF_16LE, true));
+        assert_eq!(detect(&[0xFE, 0xFF, 0, b'a']), (UTF_16BE, true));
+    }
+
+    #[test]
+    fn detect_sniffs_utf16_without_bom() {
+        let le: Vec<u8> = "hello world".encode_utf16().flat_map(|u| u.to_le_bytes()).collect();
+        let be: Vec<u8> = "hello world".encode_utf16().flat_map(|u| u.to_be_bytes()).collect();
+        assert_eq!(detect(&le), (UTF_16LE, false));
+        assert_eq!(detect(&be), (UTF_16BE, false));
+    }
+
+    #[test]
+    fn detect_accepts_plain_utf8() {
+        assert_eq!(detect("héllo wörld".as_bytes()), (UTF_8, false));
+        assert_eq!(detect(b""), (UTF_8, false));
+    }
+
+    #[test]
+    fn detect_head_accepts_character_cut_at_boundary() {
+        let mut bytes = vec![b'a'; 7999];
+        bytes.extend_from_slice("é".as_bytes());
+        assert_eq!(detect_head(&bytes[..8000], false), (UTF_8, false));
+        assert_ne!(detect_head(&bytes[..8000], true).0, UTF_8);
+        assert_ne!(detect_head(b"abc \xff", false).0, UTF_8);
+    }
+
+    #[test]
+    fn detect_guesses_legacy_encodings() {
+        let (encoding, bom) = detect(b"caf\xe9 cr\xe8me br\xfbl\xe9e, na\xefve fa\xe7ade");
+        assert_eq!(encoding, WINDOWS_1252);
+        assert!(!bom);
+    }
+
+    #[test]
+    fn decode_strips_bom() {
+        let decoded = decode(&[0xEF, 0xBB, 0xBF, b'h', b'i'], None);
+        assert_eq!(decoded.text, "hi");
+        assert!(decoded.bom);
+        assert!(!decoded.had_errors);
+    }
+
+    #[test]
+    fn decode_honours_forced_encoding() {
+        let decoded = decode(b"\x82\xa0", Some(SHIFT_JIS));
+        assert_eq!(decoded.text, "あ");
+        assert_eq!(decoded.encoding, SHIFT_JIS);
+        assert!(!decoded.bom);
+        assert!(!decoded.had_errors);
+    }
+
+    #[test]
+    fn decode_reports_lossy_input() {
+        let decoded = decode(b"ok \xff\xfe\xfd", Some(UTF_8));
+        assert!(decoded.had_errors);
+        assert!(decoded.text.contains('\u{FFFD}'));
+    }
+
+    #[test]
+    fn encode_round_trips_through_decode() {
+        for (encoding, bom) in [(UTF_8, true), (UTF_16LE, true), (UTF_16BE, false), (SHIFT_JIS, false)] {
+            let bytes = encode("あいう abc", encoding, bom).unwrap();
+            let decoded = decode(&bytes, Some(encoding));
+            assert_eq!(decoded.text, "あいう abc");
+            assert_eq!(decoded.bom, bom);
+            assert!(!decoded.had_errors);
+        }
+    }
+
+    #[test]
+    fn encode_writes_utf16_bom() {
+        assert_eq!(encode("a", UTF_16LE, true).unwrap(), vec![0xFF, 0xFE, b'a', 0]);
+        assert_eq!(encode("a", UTF_16BE, true).unwrap(), vec![0xFE, 0xFF, 0, b'a']);
+    }
+
+    #[test]
+    fn encode_rejects_unmappable_characters() {
+        assert!(encode("日本", WINDOWS_1252, false).is_err());
+    }
+
+    #[test]
+    fn lookup_accepts_labels() {
+        assert_eq!(lookup(" latin1 ").unwrap(), WINDOWS_1252);
+        assert!(lookup("not-an-encoding").is_err());
+    }
+}
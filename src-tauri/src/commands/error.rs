@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    NotFound,
+    PermissionDenied,
+    AlreadyExists,
+    Conflict,
+    NotARepository,
+    InvalidRegex,
+    InvalidInput,
+    Unsupported,
+    Io,
+    Git,
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CommandError {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub path: Option<String>,
+    pub code: Option<i32>,
+}
+
+pub type CommandResult<T> = Result<T, CommandError>;
+
+impl CommandError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        CommandError {
+            kind,
+            message: message.into(),
+            path: None,
+            code: None,
+        }
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::NotFound, message)
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Conflict, message)
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::InvalidInput, message)
+    }
+
+    pub fn other(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Other, message)
+    }
+
+    pub fn with_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.path = Some(path.as_ref().to_string_lossy().to_string());
+        self
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "{}: {}", path, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl From<std::io::Error> for CommandError {
+    fn from(e: std::io::Error) -> Self {
+        let kind = match e.kind() {
+            std::io::ErrorKind::NotFound => ErrorKind::NotFound,
+            std::io::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+            std::io::ErrorKind::AlreadyExists => ErrorKind::AlreadyExists,
+            std::io::ErrorKind::InvalidInput | std::io::ErrorKind::InvalidData => {
+                ErrorKind::InvalidInput
+            }
+            std::io::ErrorKind::Unsupported => ErrorKind::Unsupported,
+            _ => ErrorKind::Io,
+        };
+        CommandError {
+            kind,
+            message: e.to_string(),
+            path: None,
+            code: e.raw_os_error(),
+        }
+    }
+}
+
+impl From<git2::Error> for CommandError {
+    fn from(e: git2::Error) -> Self {
+        let kind = match (e.code(), e.class()) {
+            (git2::ErrorCode::NotFound, git2::ErrorClass::Repository) => ErrorKind::NotARepository,
+            (git2::ErrorCode::NotFound, _) => ErrorKind::NotFound,
+            (git2::ErrorCode::Exists, _) => ErrorKind::AlreadyExists,
+            (
+                git2::ErrorCode::Conflict
+                | git2::ErrorCode::MergeConflict
+                | git2::ErrorCode::Locked
+                | git2::ErrorCode::Modified
+                | git2::ErrorCode::Uncommitted
+                | git2::ErrorCode::NotFastForward,
+                _,
+            ) => ErrorKind::Conflict,
+            (git2::ErrorCode::Auth | git2::ErrorCode::Certificate, _) => ErrorKind::PermissionDenied,
+            (git2::ErrorCode::InvalidSpec | git2::ErrorCode::Invalid | git2::ErrorCode::Ambiguous, _) => {
+                ErrorKind::InvalidInput
+            }
+            _ => ErrorKind::Git,
+        };
+        CommandError {
+            kind,
+            message: e.message().to_string(),
+            path: None,
+            code: Some(e.raw_code()),
+        }
+    }
+}
+
+impl From<regex::Error> for CommandError {
+    fn from(e: regex::Error) -> Self {
+        Self::new(ErrorKind::InvalidRegex, format!("Invalid regex: {}", e))
+    }
+}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        Self::other(message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        Self::other(message)
+    }
+}
+
+pub trait ResultExt<T> {
+    fn with_path(self, path: impl AsRef<Path>) -> CommandResult<T>;
+}
+
+impl<T, E: Into<CommandError>> ResultExt<T> for Result<T, E> {
+    fn with_path(self, path: impl AsRef<Path>) -> CommandResult<T> {
+        self.map_err(|e| e.into().with_path(path))
+    }
+}
@@ -0,0 +1,386 @@
+use super::encoding;
+use super::error::{CommandError, CommandResult, ResultExt};
+use super::workspace::ensure_allowed;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+const SNIFF_SIZE: usize = 8000;
+const MAX_RANGE_BYTES: u64 = 4 * 1024 * 1024;
+const MAX_RANGE_LINES: usize = 10_000;
+const MAX_LINE_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileInfo {
+    pub path: String,
+    pub size: u64,
+    pub mime: String,
+    #[serde(rename = "isBinary")]
+    pub is_binary: bool,
+    pub encoding: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileChunk {
+    pub offset: u64,
+    pub bytes: Vec<u8>,
+    pub size: u64,
+    pub eof: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileLines {
+    #[serde(rename = "startLine")]
+    pub start_line: usize,
+    #[serde(rename = "startOffset")]
+    pub start_offset: u64,
+    #[serde(rename = "nextOffset")]
+    pub next_offset: u64,
+    pub lines: Vec<String>,
+    pub encoding: String,
+    pub size: u64,
+    pub eof: bool,
+}
+
+fn sniff_magic(head: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"BM", "image/bmp"),
+        (b"\x00\x00\x01\x00", "image/x-icon"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x1f\x8b", "application/gzip"),
+        (b"BZh", "application/x-bzip2"),
+        (b"\xfd7zXZ\x00", "application/x-xz"),
+        (b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+        (b"\x7fELF", "application/x-elf"),
+        (b"\x00asm", "application/wasm"),
+        (b"SQLite format 3\x00", "application/vnd.sqlite3"),
+        (b"OggS", "audio/ogg"),
+        (b"fLaC", "audio/flac"),
+        (b"ID3", "audio/mpeg"),
+    ];
+
+    if head.len() >= 12 && &head[..4] == b"RIFF" {
+        match &head[8..12] {
+            b"WEBP" => return Some("image/webp"),
+            b"WAVE" => return Some("audio/wav"),
+            _ => {}
+        }
+    }
+    if head.len() >= 12 && &head[4..8] == b"ftyp" {
+        return Some(match &head[8..12] {
+            b"avif" => "image/avif",
+            b"heic" | b"heix" => "image/heic",
+            b"qt  " => "video/quicktime",
+            _ => "video/mp4",
+        });
+    }
+    if head.len() >= 262 && &head[257..262] == b"ustar" {
+        return Some("application/x-tar");
+    }
+
+    SIGNATURES
+        .iter()
+        .find(|(magic, _)| head.starts_with(magic))
+        .map(|(_, mime)| *mime)
+}
+
+fn mime_from_extension(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_string_lossy().to_lowercase();
+    Some(match ext.as_str() {
+        "svg" => "image/svg+xml",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" | "mjs" | "cjs" => "text/javascript",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "md" | "markdown" => "text/markdown",
+        "csv" => "text/csv",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "pdf" => "application/pdf",
+        _ => return None,
+    })
+}
+
+pub(crate) fn is_binary(head: &[u8]) -> bool {
+    let (detected, _) = encoding::detect_head(head, false);
+    if detected == UTF_16LE || detected == UTF_16BE {
+        return false;
+    }
+    head.contains(&0)
+}
+
+fn read_head(file: &mut File, limit: usize) -> std::io::Result<Vec<u8>> {
+    let mut head = Vec::with_capacity(limit);
+    file.take(limit as u64).read_to_end(&mut head)?;
+    Ok(head)
+}
+
+#[tauri::command]
+pub fn file_info(path: &str) -> CommandResult<FileInfo> {
+    let resolved = ensure_allowed(path)?;
+    let mut file = File::open(&resolved).with_path(path)?;
+    let size = file.metadata().with_path(path)?.len();
+    let head = read_head(&mut file, SNIFF_SIZE).with_path(path)?;
+
+    let complete = head.len() as u64 >= size;
+    let binary = is_binary(&head);
+    let mime = sniff_magic(&head)
+        .or_else(|| mime_from_extension(Path::new(path)))
+        .unwrap_or(if binary { "application/octet-stream" } else { "text/plain" });
+    let encoding = if binary {
+        None
+    } else {
+        Some(encoding::detect_head(&head, complete).0.name().to_string())
+    };
+
+    Ok(FileInfo {
+        path: path.to_string(),
+        size,
+        mime: mime.to_string(),
+        is_binary: binary,
+        encoding,
+    })
+}
+
+#[tauri::command]
+pub fn read_file_range(path: &str, offset: u64, length: u64) -> CommandResult<FileChunk> {
+    if length > MAX_RANGE_BYTES {
+        return Err(CommandError::invalid_input(format!(
+            "Range length exceeds {} bytes",
+            MAX_RANGE_BYTES
+        ))
+        .with_path(path));
+    }
+
+    let resolved = ensure_allowed(path)?;
+    let mut file = File::open(&resolved).with_path(path)?;
+    let size = file.metadata().with_path(path)?.len();
+    let offset = offset.min(size);
+    file.seek(SeekFrom::Start(offset)).with_path(path)?;
+
+    let mut bytes = Vec::with_capacity(length.min(size - offset) as usize);
+    file.take(length).read_to_end(&mut bytes).with_path(path)?;
+    let eof = offset + bytes.len() as u64 >= size;
+
+    Ok(FileChunk {
+        offset,
+        bytes,
+        size,
+        eof,
+    })
+}
+
+struct LineReader<R: Read> {
+    reader: BufReader<R>,
+    unit: usize,
+    newline: &'static [u8],
+    offset: u64,
+}
+
+impl<R: Read> LineReader<R> {
+    fn new(reader: R, encoding: &'static Encoding, offset: u64) -> Self {
+        let (unit, newline): (usize, &'static [u8]) = if encoding == UTF_16LE {
+            (2, b"\n\x00")
+        } else if encoding == UTF_16BE {
+            (2, b"\x00\n")
+        } else {
+            (1, b"\n")
+        };
+        LineReader {
+            reader: BufReader::new(reader),
+            unit,
+            newline,
+            offset,
+        }
+    }
+
+    fn next_line(&mut self, keep: bool) -> std::io::Result<Option<Vec<u8>>> {
+        let mut line = Vec::new();
+
+        if self.unit == 1 {
+            let mut read_any = false;
+            loop {
+                let buf = self.reader.fill_buf()?;
+                if buf.is_empty() {
+                    break;
+                }
+                read_any = true;
+                let newline = memchr::memchr(b'\n', buf);
+                let content = &buf[..newline.unwrap_or(buf.len())];
+                if keep && line.len() < MAX_LINE_BYTES {
+                    let room = MAX_LINE_BYTES - line.len();
+                    line.extend_from_slice(&content[..content.len().min(room)]);
+                }
+                let consumed = newline.map_or(buf.len(), |i| i + 1);
+                self.reader.consume(consumed);
+                self.offset += consumed as u64;
+                if newline.is_some() {
+                    break;
+                }
+            }
+            return Ok(read_any.then_some(line));
+        }
+
+        let mut unit = [0u8; 2];
+        let mut read_any = false;
+
+        loop {
+            let buf = &mut unit[..self.unit];
+            match self.reader.read_exact(buf) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e),
+            }
+            read_any = true;
+            self.offset += self.unit as u64;
+            if buf == self.newline {
+                break;
+            }
+            if keep && line.len() < MAX_LINE_BYTES {
+                line.extend_from_slice(buf);
+            }
+        }
+
+        Ok(if read_any { Some(line) } else { None })
+    }
+}
+
+fn decode_line(encoding: &'static Encoding, bytes: &[u8], truncated: bool) -> String {
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    let capacity = decoder.max_utf8_buffer_length(bytes.len()).unwrap_or(bytes.len() * 3);
+    let mut text = String::with_capacity(capacity);
+    let _ = decoder.decode_to_string(bytes, &mut text, !truncated);
+    text
+}
+
+#[tauri::command]
+pub fn read_file_lines(
+    path: &str,
+    start_line: usize,
+    line_count: usize,
+    from_offset: Option<u64>,
+    from_line: Option<usize>,
+    encoding: Option<String>,
+) -> CommandResult<FileLines> {
+    let resolved = ensure_allowed(path)?;
+    let line_count = line_count.min(MAX_RANGE_LINES);
+    let mut file = File::open(&resolved).with_path(path)?;
+    let size = file.metadata().with_path(path)?.len();
+    let head = read_head(&mut file, SNIFF_SIZE).with_path(path)?;
+
+    let (text_encoding, bom) = match encoding.as_deref() {
+        Some(label) => {
+            let forced = encoding::lookup(label).with_path(path)?;
+            let bom = Encoding::for_bom(&head).is_some_and(|(e, _)| e == forced);
+            (forced, bom)
+        }
+        None => encoding::detect_head(&head, head.len() as u64 >= size),
+    };
+    let bom_len = if bom {
+        Encoding::for_bom(&head).map(|(_, len)| len as u64).unwrap_or(0)
+    } else {
+        0
+    };
+
+    let (mut offset, mut line) = match (from_offset, from_line) {
+        (Some(offset), Some(line)) if line <= start_line => (offset.max(bom_len), line),
+        _ => (bom_len, 0),
+    };
+    offset = offset.min(size);
+    file.seek(SeekFrom::Start(offset)).with_path(path)?;
+
+    let mut reader = LineReader::new(file, text_encoding, offset);
+    while line < start_line {
+        if reader.next_line(false).with_path(path)?.is_none() {
+            break;
+        }
+        line += 1;
+    }
+
+    let start_offset = reader.offset;
+    let mut lines = Vec::new();
+    let mut eof = false;
+    while lines.len() < line_count {
+        match reader.next_line(true).with_path(path)? {
+            Some(bytes) => {
+                let text = decode_line(text_encoding, &bytes, bytes.len() >= MAX_LINE_BYTES);
+                lines.push(text.trim_end_matches('\r').to_string());
+            }
+            None => {
+                eof = true;
+                break;
+            }
+        }
+    }
+    if reader.offset >= size {
+        eof = true;
+    }
+
+    Ok(FileLines {
+        start_line: line,
+        start_offset,
+        next_offset: reader.offset,
+        lines,
+        encoding: text_encoding.name().to_string(),
+        size,
+        eof,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::workspace::trust_root;
+    use std::path::PathBuf;
+
+    fn scratch_file(name: &str, bytes: &[u8]) -> (PathBuf, String) {
+        let dir = std::env::temp_dir().join(format!("litcode-file-view-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir = trust_root(&dir).unwrap();
+        let file = dir.join("file.txt");
+        std::fs::write(&file, bytes).unwrap();
+        (dir, file.to_string_lossy().to_string())
+    }
+
+    #[test]
+    fn file_info_detects_utf8_cut_at_sniff_boundary() {
+        let mut bytes = vec![b'a'; SNIFF_SIZE - 1];
+        bytes.extend_from_slice("é and more".as_bytes());
+        let (dir, file) = scratch_file("boundary", &bytes);
+
+        let info = file_info(&file).unwrap();
+        assert_eq!(info.encoding.as_deref(), Some("UTF-8"));
+        assert!(!info.is_binary);
+        let lines = read_file_lines(&file, 0, 1, None, None, None).unwrap();
+        assert_eq!(lines.encoding, "UTF-8");
+        assert!(lines.lines[0].ends_with("é and more"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn read_file_lines_truncates_on_char_boundary() {
+        let mut bytes = vec![b'a'; MAX_LINE_BYTES - 1];
+        bytes.extend_from_slice("日本\nnext\n".as_bytes());
+        let (dir, file) = scratch_file("truncate", &bytes);
+
+        let lines = read_file_lines(&file, 0, 2, None, None, None).unwrap();
+        assert_eq!(lines.lines[0].len(), MAX_LINE_BYTES - 1);
+        assert!(!lines.lines[0].contains('\u{FFFD}'));
+        assert_eq!(lines.lines[1], "next");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
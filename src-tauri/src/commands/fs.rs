@@ -1,6 +1,21 @@
+use super::editorconfig;
+use super::encoding;
+use super::error::{CommandError, CommandResult, ErrorKind, ResultExt};
+use super::git::ModeChanges;
+use super::history;
+use super::journal;
+use super::workspace::{ensure_allowed, ensure_entry_allowed};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DirEntry {
@@ -8,75 +23,766 @@ pub struct DirEntry {
     pub name: String,
     #[serde(rename = "isDirectory")]
     pub is_directory: bool,
+    pub size: u64,
+    pub mtime: u64,
+    pub mode: Option<u32>,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub readonly: bool,
+    #[serde(rename = "isSymlink")]
+    pub is_symlink: bool,
+    #[serde(rename = "symlinkTarget")]
+    pub symlink_target: Option<String>,
+    #[serde(rename = "isExecutable")]
+    pub is_executable: bool,
+    #[serde(rename = "isHidden")]
+    pub is_hidden: bool,
+    #[serde(rename = "isIgnored")]
+    pub is_ignored: bool,
+    #[serde(rename = "gitStatus")]
+    pub git_status: Option<String>,
+    pub children: Option<Vec<DirEntry>>,
+    #[serde(rename = "moreEntries")]
+    pub more_entries: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ReadDirOptions {
+    #[serde(rename = "showHidden", default)]
+    pub show_hidden: bool,
+    #[serde(rename = "hideIgnored", default)]
+    pub hide_ignored: bool,
+}
+
+#[derive(Clone)]
+pub(crate) struct GitSnapshot {
+    git_dir: PathBuf,
+    workdir: PathBuf,
+    statuses: Arc<HashMap<String, git2::Status>>,
+    folders: Arc<HashMap<String, bool>>,
+    mode_changes: Arc<ModeChanges>,
+}
+
+fn folder_rollups(statuses: &HashMap<String, git2::Status>) -> HashMap<String, bool> {
+    let mut folders: HashMap<String, bool> = HashMap::new();
+    for (path, status) in statuses {
+        for (i, _) in path.match_indices('/') {
+            let untracked = folders.entry(path[..i].to_string()).or_insert(true);
+            *untracked &= status.is_wt_new();
+        }
+    }
+    folders
+}
+
+impl GitSnapshot {
+    pub(crate) fn for_dir(dir: &Path) -> Option<Self> {
+        let repo = git2::Repository::discover(dir).ok()?;
+        let workdir = repo.workdir()?.canonicalize().ok()?;
+        let rel_dir = dir.strip_prefix(&workdir).ok()?.to_string_lossy().replace('\\', "/");
+
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true)
+            .recurse_untracked_dirs(false)
+            .include_ignored(false);
+        if !rel_dir.is_empty() {
+            opts.disable_pathspec_match(true).pathspec(&rel_dir);
+        }
+        let statuses: HashMap<String, git2::Status> = repo
+            .statuses(Some(&mut opts))
+            .map(|list| {
+                list.iter()
+                    .filter_map(|e| e.path().map(|p| (p.to_string(), e.status())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mode_changes = ModeChanges::load(&repo, statuses.iter().map(|(p, s)| (p.as_str(), *s)));
+
+        Some(GitSnapshot {
+            git_dir: repo.path().to_path_buf(),
+            workdir,
+            folders: Arc::new(folder_rollups(&statuses)),
+            statuses: Arc::new(statuses),
+            mode_changes: Arc::new(mode_changes),
+        })
+    }
+
+    pub(crate) fn open(&self) -> Option<GitListing> {
+        let repo = git2::Repository::open(&self.git_dir).ok()?;
+        Some(GitListing {
+            repo,
+            snapshot: self.clone(),
+        })
+    }
+}
+
+pub(crate) struct GitListing {
+    repo: git2::Repository,
+    snapshot: GitSnapshot,
+}
+
+impl GitListing {
+    pub(crate) fn for_dir(dir: &Path) -> Option<Self> {
+        GitSnapshot::for_dir(dir)?.open()
+    }
+
+    fn relative(&self, path: &Path) -> Option<String> {
+        let rel = path.strip_prefix(&self.snapshot.workdir).ok()?;
+        if rel.as_os_str().is_empty() {
+            return None;
+        }
+        Some(rel.to_string_lossy().replace('\\', "/"))
+    }
+
+    pub(crate) fn is_ignored(&self, path: &Path) -> bool {
+        match self.relative(path) {
+            Some(rel) => rel == ".git" || self.repo.is_path_ignored(&rel).unwrap_or(false),
+            None => false,
+        }
+    }
+
+    fn is_mode_only_change(&self, rel: &str, status: git2::Status) -> bool {
+        let changes = &self.snapshot.mode_changes;
+        (!status.is_index_modified() || changes.is_mode_only(rel, true))
+            && (!status.is_wt_modified() || changes.is_mode_only(rel, false))
+    }
+
+    pub(crate) fn status(&self, path: &Path, is_dir: bool) -> Option<String> {
+        let rel = self.relative(path)?;
+        if !is_dir {
+            let status = *self.snapshot.statuses.get(&rel)?;
+            let label = status_label(status);
+            if label == "modified" && self.is_mode_only_change(&rel, status) {
+                return Some("modeChanged".to_string());
+            }
+            return Some(label.to_string());
+        }
+
+        match self.snapshot.folders.get(&rel)? {
+            true => Some("untracked".to_string()),
+            false => Some("modified".to_string()),
+        }
+    }
+}
+
+fn status_label(status: git2::Status) -> &'static str {
+    if status.is_conflicted() {
+        "conflicted"
+    } else if status.is_wt_new() {
+        "untracked"
+    } else if status.is_index_new() {
+        "added"
+    } else if status.is_wt_deleted() || status.is_index_deleted() {
+        "deleted"
+    } else if status.is_wt_renamed() || status.is_index_renamed() {
+        "renamed"
+    } else {
+        "modified"
+    }
+}
+
+pub(crate) fn mtime_millis(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(unix)]
+pub(crate) fn permission_bits(metadata: &fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn permission_bits(_metadata: &fs::Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+pub(crate) fn ownership(metadata: &fs::Metadata) -> Option<(u32, u32)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.uid(), metadata.gid()))
+}
+
+#[cfg(not(unix))]
+pub(crate) fn ownership(_metadata: &fs::Metadata) -> Option<(u32, u32)> {
+    None
+}
+
+pub(crate) fn is_executable(path: &Path, metadata: &fs::Metadata) -> bool {
+    if metadata.is_dir() {
+        return false;
+    }
+    match permission_bits(metadata) {
+        Some(mode) => mode & 0o111 != 0,
+        None => path
+            .extension()
+            .map(|e| matches!(e.to_string_lossy().to_lowercase().as_str(), "exe" | "bat" | "cmd" | "com"))
+            .unwrap_or(false),
+    }
+}
+
+pub(crate) fn build_dir_entry(path: PathBuf, git: Option<&GitListing>) -> DirEntry {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let link_metadata = fs::symlink_metadata(&path).ok();
+    let is_symlink = link_metadata
+        .as_ref()
+        .is_some_and(|m| m.file_type().is_symlink());
+    let metadata = fs::metadata(&path).ok().or(link_metadata);
+    let symlink_target = if is_symlink {
+        fs::read_link(&path).ok().map(|t| t.to_string_lossy().to_string())
+    } else {
+        None
+    };
+
+    let is_directory = metadata.as_ref().is_some_and(|m| m.is_dir());
+    let is_ignored = git.is_some_and(|g| g.is_ignored(&path));
+    let git_status = if is_ignored {
+        None
+    } else {
+        git.and_then(|g| g.status(&path, is_directory))
+    };
+
+    DirEntry {
+        is_directory,
+        size: metadata.as_ref().filter(|m| m.is_file()).map(|m| m.len()).unwrap_or(0),
+        mtime: metadata.as_ref().map(mtime_millis).unwrap_or(0),
+        mode: metadata.as_ref().and_then(permission_bits),
+        uid: metadata.as_ref().and_then(|m| ownership(m).map(|o| o.0)),
+        gid: metadata.as_ref().and_then(|m| ownership(m).map(|o| o.1)),
+        readonly: metadata.as_ref().is_some_and(|m| m.permissions().readonly()),
+        is_symlink,
+        symlink_target,
+        is_executable: metadata.as_ref().is_some_and(|m| is_executable(&path, m)),
+        is_hidden: name.starts_with('.'),
+        is_ignored,
+        git_status,
+        children: None,
+        more_entries: None,
+        path: path.to_string_lossy().to_string(),
+        name,
+    }
+}
+
+fn sort_key(is_directory: bool, name: &str) -> (bool, String) {
+    (!is_directory, name.to_lowercase())
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct FileVersion {
+    pub mtime: u64,
+    pub size: u64,
+    pub hash: String,
+}
+
+impl FileVersion {
+    pub(crate) fn from_bytes(metadata: &fs::Metadata, bytes: &[u8]) -> Self {
+        let mtime = mtime_millis(metadata);
+        let hash = git2::Oid::hash_object(git2::ObjectType::Blob, bytes)
+            .map(|oid| oid.to_string())
+            .unwrap_or_default();
+        FileVersion {
+            mtime,
+            size: bytes.len() as u64,
+            hash,
+        }
+    }
+
+    pub(crate) fn same_content(&self, other: &FileVersion) -> bool {
+        self.size == other.size && self.hash == other.hash
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LoadedFile {
+    pub content: String,
+    pub version: FileVersion,
+    pub encoding: String,
+    pub bom: bool,
+    #[serde(rename = "hadErrors")]
+    pub had_errors: bool,
+}
+
+fn current_version(path: &Path) -> CommandResult<Option<FileVersion>> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(CommandError::from(e).with_path(path)),
+    };
+    let metadata = fs::metadata(path).with_path(path)?;
+    Ok(Some(FileVersion::from_bytes(&metadata, &bytes)))
+}
+
+fn resolve_write_target(path: &Path) -> CommandResult<PathBuf> {
+    let mut target = path.to_path_buf();
+    for _ in 0..40 {
+        match fs::read_link(&target) {
+            Ok(link) if link.is_absolute() => target = link,
+            Ok(link) => {
+                let parent = target.parent().map(Path::to_path_buf).unwrap_or_default();
+                target = parent.join(link);
+            }
+            Err(_) => break,
+        }
+    }
+    if target != path {
+        ensure_entry_allowed(&target).with_path(path)?;
+    }
+    Ok(target)
+}
+
+pub(crate) fn temp_sibling_path(target: &Path) -> PathBuf {
+    let name = target
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let count = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let temp_name = format!(".{}.litcode-{}-{}.tmp", name, std::process::id(), count);
+    target.with_file_name(temp_name)
+}
+
+pub(crate) fn atomic_write(path: &Path, bytes: &[u8]) -> CommandResult<()> {
+    let target = resolve_write_target(path)?;
+    let permissions = fs::metadata(&target).ok().map(|m| m.permissions());
+    let temp = temp_sibling_path(&target);
+
+    let result = (|| -> std::io::Result<()> {
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp)?;
+        file.write_all(bytes)?;
+        if let Some(permissions) = permissions {
+            file.set_permissions(permissions)?;
+        }
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&temp, &target)
+    })();
+
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp);
+        return Err(CommandError::from(e).with_path(path));
+    }
+
+    #[cfg(unix)]
+    if let Some(dir) = target.parent().filter(|d| !d.as_os_str().is_empty()) {
+        if let Ok(dir) = fs::File::open(dir) {
+            let _ = dir.sync_all();
+        }
+    }
+
+    Ok(())
+}
+
+fn copy_file_contents(
+    src: &Path,
+    dst: &Path,
+    progress: &mut dyn FnMut(&Path, u64),
+) -> std::io::Result<()> {
+    let mut reader = fs::File::open(src)?;
+    let permissions = reader.metadata()?.permissions();
+    let mut writer = fs::File::create(dst)?;
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = std::io::Read::read(&mut reader, &mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        writer.write_all(&buffer[..read])?;
+        progress(src, read as u64);
+    }
+    writer.set_permissions(permissions)
+}
+
+pub(crate) fn copy_recursive(
+    src: &Path,
+    dst: &Path,
+    progress: &mut dyn FnMut(&Path, u64),
+) -> std::io::Result<()> {
+    let metadata = fs::symlink_metadata(src)?;
+    if metadata.file_type().is_symlink() {
+        let link = fs::read_link(src)?;
+        #[cfg(unix)]
+        return std::os::unix::fs::symlink(link, dst);
+        #[cfg(windows)]
+        return if src.is_dir() {
+            std::os::windows::fs::symlink_dir(link, dst)
+        } else {
+            std::os::windows::fs::symlink_file(link, dst)
+        };
+    }
+    if metadata.is_dir() {
+        fs::create_dir(dst)?;
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &dst.join(entry.file_name()), progress)?;
+        }
+        fs::set_permissions(dst, metadata.permissions())
+    } else {
+        copy_file_contents(src, dst, progress)
+    }
+}
+
+pub(crate) fn move_path(
+    src: &Path,
+    dst: &Path,
+    progress: &mut dyn FnMut(&Path, u64),
+) -> std::io::Result<()> {
+    match fs::rename(src, dst) {
+        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+            let staging = temp_sibling_path(dst);
+            if let Err(e) = copy_recursive(src, &staging, progress).and_then(|_| fs::rename(&staging, dst)) {
+                let _ = remove_path_permanently(&staging);
+                return Err(e);
+            }
+            remove_path_permanently(src)
+        }
+        result => result,
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn is_same_entry(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::symlink_metadata(a), fs::symlink_metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn is_same_entry(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a.to_string_lossy().to_lowercase() == b.to_string_lossy().to_lowercase(),
+        _ => false,
+    }
+}
+
+pub(crate) fn remove_path_permanently(path: &Path) -> std::io::Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+    if metadata.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
 }
 
 #[tauri::command]
-pub fn read_file(path: &str) -> Result<String, String> {
-    fs::read_to_string(path).map_err(|e| e.to_string())
+pub fn read_file(path: &str) -> CommandResult<String> {
+    load_file(path, None).map(|loaded| loaded.content)
 }
 
 #[tauri::command]
-pub fn write_file(path: &str, content: &str) -> Result<(), String> {
-    fs::write(path, content).map_err(|e| e.to_string())
+pub fn load_file(path: &str, encoding: Option<String>) -> CommandResult<LoadedFile> {
+    let resolved = ensure_allowed(path)?;
+    let forced = match encoding.as_deref() {
+        Some(label) => Some(encoding::lookup(label).with_path(path)?),
+        None => None,
+    };
+    let bytes = fs::read(&resolved).with_path(path)?;
+    let metadata = fs::metadata(&resolved).with_path(path)?;
+    let version = FileVersion::from_bytes(&metadata, &bytes);
+    let decoded = encoding::decode(&bytes, forced);
+    Ok(LoadedFile {
+        content: decoded.text,
+        version,
+        encoding: decoded.encoding.name().to_string(),
+        bom: decoded.bom,
+        had_errors: decoded.had_errors,
+    })
 }
 
 #[tauri::command]
-pub fn read_dir(path: &str) -> Result<Vec<DirEntry>, String> {
+pub fn write_file(
+    path: &str,
+    content: &str,
+    expected_version: Option<FileVersion>,
+    encoding: Option<String>,
+    bom: Option<bool>,
+    apply_editor_config: Option<bool>,
+    allow_lossy: Option<bool>,
+) -> CommandResult<FileVersion> {
     let path = Path::new(path);
-    let mut entries = Vec::new();
+    let resolved = ensure_allowed(path)?;
+    let content = if apply_editor_config.unwrap_or(false) {
+        Cow::Owned(editorconfig::apply_whitespace_rules(content, &editorconfig::resolve(&resolved)))
+    } else {
+        Cow::Borrowed(content)
+    };
+    let forced = match encoding.as_deref() {
+        Some(label) => Some(encoding::lookup(label).with_path(path)?),
+        None => None,
+    };
+    let target_encoding = forced.unwrap_or(encoding_rs::UTF_8);
+    let bytes = encoding::encode(&content, target_encoding, bom.unwrap_or(false)).with_path(path)?;
+
+    if let Some(expected) = expected_version {
+        let unchanged = current_version(&resolved)?.is_some_and(|current| current.same_content(&expected));
+        if !unchanged {
+            return Err(
+                CommandError::conflict("File has changed on disk since it was loaded").with_path(path),
+            );
+        }
+    }
+
+    if let Ok(previous) = fs::read(&resolved) {
+        if previous != bytes {
+            let decoded = encoding::decode(&previous, forced);
+            if decoded.had_errors && !allow_lossy.unwrap_or(false) {
+                return Err(CommandError::invalid_input(format!(
+                    "File is not valid {}; saving would replace the bytes that could not be decoded",
+                    decoded.encoding.name()
+                ))
+                .with_path(path));
+            }
+            history::record_snapshot(&resolved, &previous, "save")?;
+        }
+    }
+
+    atomic_write(&resolved, &bytes)?;
 
-    let read_dir = fs::read_dir(path).map_err(|e| e.to_string())?;
+    let metadata = fs::metadata(&resolved).with_path(path)?;
+    Ok(FileVersion::from_bytes(&metadata, &bytes))
+}
+
+pub(crate) fn list_dir_entries(
+    path: &Path,
+    dir: &Path,
+    git: Option<&GitListing>,
+    options: &ReadDirOptions,
+) -> CommandResult<Vec<DirEntry>> {
+    list_dir_page(path, dir, git, options, 0, usize::MAX).map(|(entries, _)| entries)
+}
+
+pub(crate) fn list_dir_page(
+    path: &Path,
+    dir: &Path,
+    git: Option<&GitListing>,
+    options: &ReadDirOptions,
+    offset: usize,
+    limit: usize,
+) -> CommandResult<(Vec<DirEntry>, usize)> {
+    let mut names = Vec::new();
+
+    let read_dir = fs::read_dir(path).with_path(path)?;
 
     for entry in read_dir {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let file_path = entry.path();
+        let entry = entry.with_path(path)?;
         let file_name = entry.file_name().to_string_lossy().to_string();
-        
-        if file_name.starts_with('.') {
+
+        if file_name == ".git" || (!options.show_hidden && file_name.starts_with('.')) {
+            continue;
+        }
+        if options.hide_ignored && git.is_some_and(|g| g.is_ignored(&dir.join(&file_name))) {
             continue;
         }
 
-        let is_dir = file_path.is_dir();
-
-        entries.push(DirEntry {
-            path: file_path.to_string_lossy().to_string(),
-            name: file_name,
-            is_directory: is_dir,
-        });
+        let is_directory = match entry.file_type() {
+            Ok(file_type) if file_type.is_symlink() => fs::metadata(entry.path()).is_ok_and(|m| m.is_dir()),
+            Ok(file_type) => file_type.is_dir(),
+            Err(_) => false,
+        };
+        names.push((sort_key(is_directory, &file_name), file_name, entry.path()));
     }
 
-    entries.sort_by(|a, b| {
-        match (a.is_directory, b.is_directory) {
-            (true, false) => std::cmp::Ordering::Less,
-            (false, true) => std::cmp::Ordering::Greater,
-            _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-        }
-    });
+    names.sort_by(|a, b| a.0.cmp(&b.0));
+    let remaining = names.len().saturating_sub(offset).saturating_sub(limit);
+
+    let entries = names
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .map(|(_, file_name, entry_path)| {
+            let mut dir_entry = build_dir_entry(dir.join(&file_name), git);
+            dir_entry.path = entry_path.to_string_lossy().to_string();
+            dir_entry
+        })
+        .collect();
 
-    Ok(entries)
+    Ok((entries, remaining))
+}
+
+#[tauri::command]
+pub fn read_dir(path: &str, options: Option<ReadDirOptions>) -> CommandResult<Vec<DirEntry>> {
+    let options = options.unwrap_or_default();
+    let path = Path::new(path);
+    let dir = ensure_allowed(path)?;
+    let git = GitListing::for_dir(&dir);
+    list_dir_entries(path, &dir, git.as_ref(), &options)
 }
 
 #[tauri::command]
 pub fn file_exists(path: &str) -> bool {
-    Path::new(path).exists()
+    ensure_entry_allowed(path).is_ok_and(|resolved| resolved.exists())
 }
 
 #[tauri::command]
-pub fn create_dir(path: &str) -> Result<(), String> {
-    fs::create_dir_all(path).map_err(|e| e.to_string())
+pub fn create_dir(path: &str) -> CommandResult<()> {
+    let resolved = ensure_allowed(path)?;
+    let top = resolved
+        .ancestors()
+        .take_while(|p| fs::symlink_metadata(p).is_err())
+        .last()
+        .map(Path::to_path_buf);
+    fs::create_dir_all(&resolved).with_path(path)?;
+    if let Some(top) = top {
+        journal::record_create_dir(&top, &resolved);
+    }
+    Ok(())
+}
+
+pub(crate) fn create_new_file(path: &Path, content: &[u8]) -> CommandResult<PathBuf> {
+    let resolved = ensure_entry_allowed(path)?;
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&resolved)
+        .with_path(path)?;
+    file.write_all(content).with_path(path)?;
+    Ok(resolved)
 }
 
 #[tauri::command]
-pub fn remove_path(path: &str) -> Result<(), String> {
+pub fn create_file(path: &str, content: Option<String>) -> CommandResult<FileVersion> {
     let path = Path::new(path);
-    if path.is_dir() {
-        fs::remove_dir_all(path).map_err(|e| e.to_string())
-    } else {
-        fs::remove_file(path).map_err(|e| e.to_string())
-    }
+    let content = content.unwrap_or_default();
+    let resolved = create_new_file(path, content.as_bytes())?;
+    journal::record_create_file(path, content.as_bytes());
+    let metadata = fs::metadata(&resolved).with_path(path)?;
+    Ok(FileVersion::from_bytes(&metadata, content.as_bytes()))
 }
 
 #[tauri::command]
-pub fn rename_path(old_path: &str, new_path: &str) -> Result<(), String> {
-    fs::rename(old_path, new_path).map_err(|e| e.to_string())
+pub fn rename_path(old_path: &str, new_path: &str) -> CommandResult<()> {
+    let old = Path::new(old_path);
+    let new = Path::new(new_path);
+    rename_entry(old, new)?;
+    journal::record_rename(old, new);
+    Ok(())
+}
+
+pub(crate) fn rename_entry(old: &Path, new: &Path) -> CommandResult<()> {
+    let resolved_old = ensure_entry_allowed(old)?;
+    let resolved_new = ensure_entry_allowed(new)?;
+    if fs::symlink_metadata(&resolved_new).is_ok() && !is_same_entry(&resolved_old, &resolved_new) {
+        return Err(CommandError::new(ErrorKind::AlreadyExists, "Destination already exists").with_path(new));
+    }
+    move_path(&resolved_old, &resolved_new, &mut |_, _| {}).with_path(old)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::workspace::trust_root;
+
+    fn scratch_dir(name: &str, trusted: bool) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("litcode-fs-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        if trusted {
+            trust_root(&dir).unwrap()
+        } else {
+            dir.canonicalize().unwrap()
+        }
+    }
+
+    fn write(path: &Path, content: &str) -> CommandResult<FileVersion> {
+        write_file(path.to_str().unwrap(), content, None, None, None, None, None)
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_file_refuses_dangling_symlink_out_of_workspace() {
+        let root = scratch_dir("dangling-out", true);
+        let outside = scratch_dir("dangling-target", false);
+        std::os::unix::fs::symlink(outside.join("escape.txt"), root.join("link.txt")).unwrap();
+
+        let err = write(&root.join("link.txt"), "data").unwrap_err();
+        assert_eq!(err.kind, ErrorKind::PermissionDenied);
+        assert!(!outside.join("escape.txt").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+        fs::remove_dir_all(&outside).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_file_follows_symlink_inside_workspace() {
+        let root = scratch_dir("link-in", true);
+        std::os::unix::fs::symlink("target.txt", root.join("link.txt")).unwrap();
+
+        let version = write(&root.join("link.txt"), "data").unwrap();
+        assert_eq!(fs::read_to_string(root.join("target.txt")).unwrap(), "data");
+        assert!(fs::symlink_metadata(root.join("link.txt")).unwrap().file_type().is_symlink());
+        assert_eq!(current_version(&root.join("target.txt")).unwrap(), Some(version));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn write_file_rejects_stale_version() {
+        let root = scratch_dir("stale", true);
+        let path = root.join("file.txt");
+        let version = write(&path, "one").unwrap();
+        fs::write(&path, "changed elsewhere").unwrap();
+
+        let err = write_file(path.to_str().unwrap(), "two", Some(version), None, None, None, None).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Conflict);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "changed elsewhere");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    fn statuses(dir: &Path) -> HashMap<String, Option<String>> {
+        read_dir(dir.to_str().unwrap(), None)
+            .unwrap()
+            .into_iter()
+            .map(|entry| (entry.name, entry.git_status))
+            .collect()
+    }
+
+    #[test]
+    fn read_dir_rolls_up_git_status_per_folder() {
+        let root = scratch_dir("git-status", true);
+        let repo = git2::Repository::init(&root).unwrap();
+        fs::create_dir_all(root.join("a")).unwrap();
+        fs::create_dir_all(root.join("[a]")).unwrap();
+        fs::write(root.join("a/tracked.txt"), "one").unwrap();
+        fs::write(root.join("[a]/tracked.txt"), "one").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[]).unwrap();
+
+        fs::write(root.join("a/tracked.txt"), "two").unwrap();
+        fs::create_dir_all(root.join("new")).unwrap();
+        fs::write(root.join("new/file.txt"), "new").unwrap();
+
+        let top = statuses(&root);
+        assert_eq!(top["a"].as_deref(), Some("modified"));
+        assert_eq!(top["[a]"], None);
+        assert_eq!(top["new"].as_deref(), Some("untracked"));
+        assert_eq!(statuses(&root.join("a"))["tracked.txt"].as_deref(), Some("modified"));
+        assert_eq!(statuses(&root.join("[a]"))["tracked.txt"], None);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn create_file_refuses_existing_entry() {
+        let root = scratch_dir("create", true);
+        let path = root.join("file.txt");
+        create_file(path.to_str().unwrap(), Some("one".to_string())).unwrap();
+        assert!(create_file(path.to_str().unwrap(), None).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "one");
+        fs::remove_dir_all(&root).unwrap();
+    }
 }
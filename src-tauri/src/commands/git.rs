@@ -1,4 +1,9 @@
-use git2::{DiffOptions, Repository, StatusOptions};
+use super::encoding;
+use super::error::{CommandError, CommandResult, ErrorKind, ResultExt};
+use super::fs::atomic_write;
+use super::history;
+use super::workspace::ensure_allowed;
+use git2::{DiffOptions, Oid, Repository, ResetType, StatusOptions};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -16,12 +21,27 @@ pub struct GitFileStatus {
     pub staged: bool,
     #[serde(rename = "workingTree")]
     pub working_tree: bool,
+    pub lfs: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GitDiff {
     pub path: String,
     pub hunks: Vec<GitHunk>,
+    pub lfs: Option<GitLfsDiff>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitLfsPointer {
+    pub oid: String,
+    pub size: u64,
+    pub available: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitLfsDiff {
+    pub old: Option<GitLfsPointer>,
+    pub new: Option<GitLfsPointer>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -48,9 +68,130 @@ pub struct GitDiffLine {
     pub new_line_number: Option<u32>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitReflogEntry {
+    #[serde(rename = "refName")]
+    pub ref_name: String,
+    pub index: usize,
+    #[serde(rename = "oldId")]
+    pub old_id: String,
+    #[serde(rename = "newId")]
+    pub new_id: String,
+    pub message: String,
+    pub committer: String,
+    pub time: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitReflogUndoPreview {
+    #[serde(rename = "refName")]
+    pub ref_name: String,
+    #[serde(rename = "currentId")]
+    pub current_id: String,
+    #[serde(rename = "targetId")]
+    pub target_id: String,
+    pub message: String,
+    #[serde(rename = "updatesWorkingTree")]
+    pub updates_working_tree: bool,
+    #[serde(rename = "discardedCommits")]
+    pub discarded_commits: usize,
+    pub files: Vec<GitFileStatus>,
+    #[serde(rename = "blockingFiles")]
+    pub blocking_files: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitSignatureStatus {
+    pub status: String,
+    pub format: Option<String>,
+    pub signer: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitCommit {
+    pub id: String,
+    pub summary: String,
+    pub message: String,
+    pub author: String,
+    pub email: String,
+    pub time: i64,
+    pub parents: Vec<String>,
+    pub signature: GitSignatureStatus,
+}
+
+pub(crate) fn open_repo(repo_path: &str) -> CommandResult<Repository> {
+    let resolved = ensure_allowed(repo_path)?;
+    Repository::open(&resolved).map_err(|e| {
+        let mut error = CommandError::from(e);
+        if error.kind == ErrorKind::NotFound {
+            error.kind = ErrorKind::NotARepository;
+        }
+        error.with_path(repo_path)
+    })
+}
+
+#[derive(Default)]
+struct HunkCollector {
+    hunks: Vec<GitHunk>,
+    current_lines: Vec<GitDiffLine>,
+    current_hunk: Option<(u32, u32, u32, u32)>,
+}
+
+impl HunkCollector {
+    fn push(&mut self, hunk: Option<git2::DiffHunk>, line: git2::DiffLine) {
+        if let Some(h) = hunk {
+            if self.current_hunk.is_none()
+                || self.current_hunk
+                    != Some((h.old_start(), h.old_lines(), h.new_start(), h.new_lines()))
+            {
+                self.flush();
+                self.current_hunk = Some((
+                    h.old_start(),
+                    h.old_lines(),
+                    h.new_start(),
+                    h.new_lines(),
+                ));
+            }
+        }
+
+        let line_type = match line.origin() {
+            '+' => "add",
+            '-' => "delete",
+            ' ' => "context",
+            _ => return,
+        };
+
+        let content = String::from_utf8_lossy(line.content()).to_string();
+
+        self.current_lines.push(GitDiffLine {
+            line_type: line_type.to_string(),
+            content,
+            old_line_number: line.old_lineno(),
+            new_line_number: line.new_lineno(),
+        });
+    }
+
+    fn flush(&mut self) {
+        if let Some((old_start, old_lines, new_start, new_lines)) = self.current_hunk.take() {
+            self.hunks.push(GitHunk {
+                old_start,
+                old_lines,
+                new_start,
+                new_lines,
+                lines: std::mem::take(&mut self.current_lines),
+            });
+        }
+    }
+
+    fn finish(mut self) -> Vec<GitHunk> {
+        self.flush();
+        self.hunks
+    }
+}
+
 #[tauri::command]
-pub fn git_status(repo_path: &str) -> Result<GitStatus, String> {
-    let repo = Repository::open(repo_path).map_err(|e| e.to_string())?;
+pub fn git_status(repo_path: &str) -> CommandResult<GitStatus> {
+    let repo = open_repo(repo_path)?;
 
     let head = repo.head().ok();
     let branch = head
@@ -64,7 +205,12 @@ pub fn git_status(repo_path: &str) -> Result<GitStatus, String> {
         .recurse_untracked_dirs(true)
         .include_ignored(false);
 
-    let statuses = repo.statuses(Some(&mut opts)).map_err(|e| e.to_string())?;
+    let statuses = repo.statuses(Some(&mut opts))?;
+    let modified: Vec<(String, git2::Status)> = statuses
+        .iter()
+        .filter_map(|e| Some((e.path()?.to_string(), e.status())))
+        .collect();
+    let mode_changes = ModeChanges::load(&repo, modified.iter().map(|(p, s)| (p.as_str(), *s)));
 
     let mut files: Vec<GitFileStatus> = Vec::new();
     
@@ -74,6 +220,7 @@ pub fn git_status(repo_path: &str) -> Result<GitStatus, String> {
             None => continue,
         };
         let status = entry.status();
+        let lfs = is_lfs_tracked(&repo, &path);
         
         let has_staged = status.is_index_new()
             || status.is_index_modified()
@@ -88,6 +235,8 @@ pub fn git_status(repo_path: &str) -> Result<GitStatus, String> {
         if has_staged {
             let staged_status = if status.is_index_new() {
                 "added"
+            } else if status.is_index_modified() && mode_changes.is_mode_only(&path, true) {
+                "modeChanged"
             } else if status.is_index_modified() {
                 "modified"
             } else if status.is_index_deleted() {
@@ -102,12 +251,15 @@ pub fn git_status(repo_path: &str) -> Result<GitStatus, String> {
                 status: staged_status.to_string(),
                 staged: true,
                 working_tree: false,
+                lfs,
             });
         }
         
         if has_unstaged {
             let unstaged_status = if status.is_wt_new() {
                 "untracked"
+            } else if status.is_wt_modified() && mode_changes.is_mode_only(&path, false) {
+                "modeChanged"
             } else if status.is_wt_modified() {
                 "modified"
             } else if status.is_wt_deleted() {
@@ -122,6 +274,7 @@ pub fn git_status(repo_path: &str) -> Result<GitStatus, String> {
                 status: unstaged_status.to_string(),
                 staged: false,
                 working_tree: true,
+                lfs,
             });
         }
     }
@@ -135,8 +288,12 @@ pub fn git_status(repo_path: &str) -> Result<GitStatus, String> {
 }
 
 #[tauri::command]
-pub fn git_diff(repo_path: &str, file_path: &str, staged: bool) -> Result<GitDiff, String> {
-    let repo = Repository::open(repo_path).map_err(|e| e.to_string())?;
+pub fn git_diff(repo_path: &str, file_path: &str, staged: bool) -> CommandResult<GitDiff> {
+    let repo = open_repo(repo_path)?;
+
+    if is_lfs_tracked(&repo, file_path) {
+        return lfs_diff(&repo, file_path, staged);
+    }
 
     let mut diff_opts = DiffOptions::new();
     diff_opts.pathspec(file_path);
@@ -144,201 +301,257 @@ pub fn git_diff(repo_path: &str, file_path: &str, staged: bool) -> Result<GitDif
     let diff = if staged {
         let head = repo.head().ok();
         let tree = head.as_ref().and_then(|h| h.peel_to_tree().ok());
-        repo.diff_tree_to_index(tree.as_ref(), None, Some(&mut diff_opts))
-            .map_err(|e| e.to_string())?
+        repo.diff_tree_to_index(tree.as_ref(), None, Some(&mut diff_opts))?
     } else {
-        repo.diff_index_to_workdir(None, Some(&mut diff_opts))
-            .map_err(|e| e.to_string())?
+        repo.diff_index_to_workdir(None, Some(&mut diff_opts))?
     };
 
-    let mut hunks: Vec<GitHunk> = Vec::new();
-    let mut current_lines: Vec<GitDiffLine> = Vec::new();
-    let mut current_hunk: Option<(u32, u32, u32, u32)> = None;
-
+    let mut collector = HunkCollector::default();
     diff.print(git2::DiffFormat::Patch, |_delta, hunk, line| {
-        if let Some(h) = hunk {
-            if let Some((old_start, old_lines, new_start, new_lines)) = current_hunk.take() {
-                hunks.push(GitHunk {
-                    old_start,
-                    old_lines,
-                    new_start,
-                    new_lines,
-                    lines: current_lines.clone(),
-                });
-                current_lines.clear();
-            }
-            current_hunk = Some((
-                h.old_start(),
-                h.old_lines(),
-                h.new_start(),
-                h.new_lines(),
-            ));
-        }
-
-        let line_type = match line.origin() {
-            '+' => "add",
-            '-' => "delete",
-            _ => "context",
-        };
-
-        let content = String::from_utf8_lossy(line.content()).to_string();
-
-        current_lines.push(GitDiffLine {
-            line_type: line_type.to_string(),
-            content,
-            old_line_number: line.old_lineno(),
-            new_line_number: line.new_lineno(),
-        });
-
+        collector.push(hunk, line);
         true
-    })
-    .map_err(|e| e.to_string())?;
-
-    if let Some((old_start, old_lines, new_start, new_lines)) = current_hunk {
-        hunks.push(GitHunk {
-            old_start,
-            old_lines,
-            new_start,
-            new_lines,
-            lines: current_lines,
-        });
-    }
+    })?;
 
     Ok(GitDiff {
         path: file_path.to_string(),
-        hunks,
+        hunks: collector.finish(),
+        lfs: None,
     })
 }
 
 #[tauri::command]
-pub fn git_revert_file(repo_path: &str, file_path: &str) -> Result<(), String> {
-    let repo = Repository::open(repo_path).map_err(|e| e.to_string())?;
-    let head = repo.head().map_err(|e| e.to_string())?;
-    let tree = head.peel_to_tree().map_err(|e| e.to_string())?;
+pub fn git_revert_file(repo_path: &str, file_path: &str) -> CommandResult<()> {
+    let repo = open_repo(repo_path)?;
+    let head = repo.head()?;
+    let tree = head.peel_to_tree()?;
 
     let mut checkout_builder = git2::build::CheckoutBuilder::new();
     checkout_builder.path(file_path);
     checkout_builder.force();
 
-    repo.checkout_tree(tree.as_object(), Some(&mut checkout_builder))
-        .map_err(|e| e.to_string())?;
+    repo.checkout_tree(tree.as_object(), Some(&mut checkout_builder))?;
 
     Ok(())
 }
 
-#[tauri::command]
-pub fn git_revert_hunk(repo_path: &str, file_path: &str, hunk_index: usize) -> Result<(), String> {
-    let diff = git_diff(repo_path, file_path, false)?;
-    
-    if hunk_index >= diff.hunks.len() {
-        return Err("Hunk index out of bounds".to_string());
+fn line_ending(line: &str) -> &str {
+    if line.ends_with("\r\n") {
+        "\r\n"
+    } else if line.ends_with('\n') {
+        "\n"
+    } else {
+        ""
     }
+}
+
+fn dominant_line_ending(lines: &[&str]) -> &'static str {
+    let crlf = lines.iter().filter(|l| line_ending(l) == "\r\n").count();
+    let lf = lines.iter().filter(|l| line_ending(l) == "\n").count();
+    if crlf > lf {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+fn worktree_line_ending(repo: &Repository, file_path: &str) -> Option<&'static str> {
+    let path = std::path::Path::new(file_path);
+    let flags = git2::AttrCheckFlags::FILE_THEN_INDEX;
+    let text = repo
+        .get_attr_bytes(path, "text", flags)
+        .map(git2::AttrValue::from_bytes)
+        .unwrap_or(git2::AttrValue::Unspecified);
+    if matches!(text, git2::AttrValue::False) {
+        return None;
+    }
+    if matches!(
+        repo.get_attr_bytes(path, "binary", flags)
+            .map(git2::AttrValue::from_bytes),
+        Ok(git2::AttrValue::True)
+    ) {
+        return None;
+    }
+
+    match repo.get_attr(path, "eol", flags) {
+        Ok(Some("crlf")) => return Some("\r\n"),
+        Ok(Some("lf")) => return Some("\n"),
+        _ => {}
+    }
+
+    let config = repo.config().ok()?;
+    let autocrlf = config.get_string("core.autocrlf").unwrap_or_default();
+    let converts = matches!(text, git2::AttrValue::True | git2::AttrValue::String(_))
+        || autocrlf == "true"
+        || autocrlf == "input";
+    if !converts {
+        return None;
+    }
+
+    match autocrlf.as_str() {
+        "true" => Some("\r\n"),
+        "input" => Some("\n"),
+        _ => match config.get_string("core.eol").unwrap_or_default().as_str() {
+            "crlf" => Some("\r\n"),
+            "lf" => Some("\n"),
+            _ if cfg!(windows) => Some("\r\n"),
+            _ => Some("\n"),
+        },
+    }
+}
+
+fn restored_line(content: &str, eol: Option<&str>) -> String {
+    let ending = line_ending(content);
+    match eol {
+        Some(eol) if !ending.is_empty() => {
+            format!("{}{}", &content[..content.len() - ending.len()], eol)
+        }
+        _ => content.to_string(),
+    }
+}
+
+fn apply_line_reverts(
+    current_content: &str,
+    removals: &std::collections::HashSet<usize>,
+    mut insertions: Vec<(usize, String)>,
+    eol: Option<&str>,
+) -> String {
+    let current_lines: Vec<&str> = current_content.split_inclusive('\n').collect();
+    let fallback_ending = eol.unwrap_or_else(|| dominant_line_ending(&current_lines));
+
+    insertions.sort_by_key(|(pos, _)| *pos);
 
-    let full_path = std::path::Path::new(repo_path).join(file_path);
-    let current_content = std::fs::read_to_string(&full_path).map_err(|e| e.to_string())?;
-    let current_lines: Vec<&str> = current_content.lines().collect();
-    
-    let hunk = &diff.hunks[hunk_index];
     let mut result_lines: Vec<String> = Vec::new();
-    
-    let hunk_start = (hunk.new_start as usize).saturating_sub(1);
-    let hunk_end = hunk_start + hunk.new_lines as usize;
-    
-    for (i, line) in current_lines.iter().enumerate() {
-        if i < hunk_start || i >= hunk_end {
-            result_lines.push(line.to_string());
-        } else if i == hunk_start {
-            for diff_line in &hunk.lines {
-                if diff_line.line_type == "delete" || diff_line.line_type == "context" {
-                    result_lines.push(diff_line.content.trim_end().to_string());
-                }
+    let mut insertions = insertions.into_iter().peekable();
+    for i in 0..=current_lines.len() {
+        while let Some((_, line)) = insertions.next_if(|(pos, _)| *pos <= i) {
+            result_lines.push(line);
+        }
+        if let Some(line) = current_lines.get(i) {
+            if !removals.contains(&i) {
+                result_lines.push(line.to_string());
             }
         }
     }
 
-    let result = result_lines.join("\n");
-    if current_content.ends_with('\n') && !result.is_empty() {
-        std::fs::write(&full_path, format!("{}\n", result)).map_err(|e| e.to_string())?;
-    } else {
-        std::fs::write(&full_path, result).map_err(|e| e.to_string())?;
+    let last = result_lines.len().saturating_sub(1);
+    for line in result_lines.iter_mut().take(last) {
+        if line_ending(line).is_empty() {
+            line.push_str(fallback_ending);
+        }
     }
 
-    Ok(())
+    result_lines.concat()
 }
 
-#[tauri::command]
-pub fn git_revert_lines(
+fn hunk_line_anchors(hunk: &GitHunk) -> Vec<usize> {
+    let mut next_new = if hunk.new_lines == 0 {
+        hunk.new_start as usize
+    } else {
+        (hunk.new_start as usize).saturating_sub(1)
+    };
+
+    hunk.lines
+        .iter()
+        .map(|line| {
+            if let Some(new_num) = line.new_line_number {
+                next_new = new_num as usize;
+                (new_num as usize).saturating_sub(1)
+            } else {
+                next_new
+            }
+        })
+        .collect()
+}
+
+fn revert_diff_lines<F>(
     repo_path: &str,
     file_path: &str,
-    start_line: u32,
-    end_line: u32,
-) -> Result<(), String> {
-    let diff = git_diff(repo_path, file_path, false)?;
-    let full_path = std::path::Path::new(repo_path).join(file_path);
-    let current_content = std::fs::read_to_string(&full_path).map_err(|e| e.to_string())?;
-    let current_lines: Vec<&str> = current_content.lines().collect();
-    
-    let mut lines_to_remove: std::collections::HashSet<usize> = std::collections::HashSet::new();
-    let mut lines_to_restore: Vec<(usize, String)> = Vec::new();
-    
-    for hunk in &diff.hunks {
-        for diff_line in &hunk.lines {
-            if let Some(new_num) = diff_line.new_line_number {
-                if new_num >= start_line && new_num <= end_line && diff_line.line_type == "add" {
-                    lines_to_remove.insert((new_num - 1) as usize);
-                }
+    hunks: &[&GitHunk],
+    selected: F,
+) -> CommandResult<()>
+where
+    F: Fn(&GitDiffLine) -> bool,
+{
+    let repo = open_repo(repo_path)?;
+    let eol = worktree_line_ending(&repo, file_path);
+
+    let full_path = ensure_allowed(std::path::Path::new(repo_path).join(file_path))?;
+    let previous = std::fs::read(&full_path).with_path(&full_path)?;
+    let decoded = encoding::decode(&previous, None);
+    if decoded.had_errors {
+        return Err(CommandError::invalid_input(format!(
+            "File is not valid {}; reverting would replace the bytes that could not be decoded",
+            decoded.encoding.name()
+        ))
+        .with_path(&full_path));
+    }
+
+    let mut removals: std::collections::HashSet<usize> = std::collections::HashSet::new();
+    let mut insertions: Vec<(usize, String)> = Vec::new();
+
+    for hunk in hunks {
+        let anchors = hunk_line_anchors(hunk);
+        for (diff_line, anchor) in hunk.lines.iter().zip(anchors) {
+            if !selected(diff_line) {
+                continue;
             }
-            if let (Some(_old_num), Some(new_num)) = (diff_line.old_line_number, diff_line.new_line_number) {
-                if new_num >= start_line && new_num <= end_line && diff_line.line_type == "delete" {
-                    lines_to_restore.push(((new_num - 1) as usize, diff_line.content.trim_end().to_string()));
+            match diff_line.line_type.as_str() {
+                "add" => {
+                    removals.insert(anchor);
                 }
-            }
-            if diff_line.line_type == "delete" && diff_line.new_line_number.is_none() {
-                if let Some(old_num) = diff_line.old_line_number {
-                    if old_num >= start_line && old_num <= end_line {
-                        let insert_at = (old_num - 1) as usize;
-                        lines_to_restore.push((insert_at, diff_line.content.trim_end().to_string()));
-                    }
+                "delete" => {
+                    insertions.push((anchor, restored_line(&diff_line.content, eol)));
                 }
+                _ => {}
             }
         }
     }
-    
-    let mut result_lines: Vec<String> = Vec::new();
-    
-    lines_to_restore.sort_by_key(|(pos, _)| *pos);
-    
-    let mut restore_idx = 0;
-    for (i, line) in current_lines.iter().enumerate() {
-        while restore_idx < lines_to_restore.len() && lines_to_restore[restore_idx].0 == i {
-            result_lines.push(lines_to_restore[restore_idx].1.clone());
-            restore_idx += 1;
-        }
-        
-        if !lines_to_remove.contains(&i) {
-            result_lines.push(line.to_string());
-        }
-    }
-    
-    while restore_idx < lines_to_restore.len() {
-        result_lines.push(lines_to_restore[restore_idx].1.clone());
-        restore_idx += 1;
-    }
 
-    let result = result_lines.join("\n");
-    if current_content.ends_with('\n') && !result.is_empty() {
-        std::fs::write(&full_path, format!("{}\n", result)).map_err(|e| e.to_string())?;
-    } else {
-        std::fs::write(&full_path, result).map_err(|e| e.to_string())?;
+    let result = apply_line_reverts(&decoded.text, &removals, insertions, eol);
+    let bytes = encoding::encode(&result, decoded.encoding, decoded.bom).with_path(&full_path)?;
+    if bytes == previous {
+        return Ok(());
     }
+    history::record_snapshot(&full_path, &previous, "revert")?;
+    atomic_write(&full_path, &bytes)
+}
 
-    Ok(())
+#[tauri::command]
+pub fn git_revert_hunk(repo_path: &str, file_path: &str, hunk_index: usize) -> CommandResult<()> {
+    let diff = git_diff(repo_path, file_path, false)?;
+
+    let hunk = diff
+        .hunks
+        .get(hunk_index)
+        .ok_or_else(|| CommandError::invalid_input("Hunk index out of bounds"))?;
+
+    revert_diff_lines(repo_path, file_path, &[hunk], |_| true)
+}
+
+#[tauri::command]
+pub fn git_revert_lines(
+    repo_path: &str,
+    file_path: &str,
+    start_line: u32,
+    end_line: u32,
+) -> CommandResult<()> {
+    let diff = git_diff(repo_path, file_path, false)?;
+    let hunks: Vec<&GitHunk> = diff.hunks.iter().collect();
+    let in_range = |n: Option<u32>| n.map(|n| n >= start_line && n <= end_line).unwrap_or(false);
+
+    revert_diff_lines(repo_path, file_path, &hunks, |diff_line| {
+        match diff_line.line_type.as_str() {
+            "add" => in_range(diff_line.new_line_number),
+            "delete" => in_range(diff_line.old_line_number),
+            _ => false,
+        }
+    })
 }
 
 #[tauri::command]
-pub fn git_diff_untracked(repo_path: &str, file_path: &str) -> Result<GitDiff, String> {
-    let full_path = std::path::Path::new(repo_path).join(file_path);
-    let content = std::fs::read_to_string(&full_path).map_err(|e| e.to_string())?;
+pub fn git_diff_untracked(repo_path: &str, file_path: &str) -> CommandResult<GitDiff> {
+    let full_path = ensure_allowed(std::path::Path::new(repo_path).join(file_path))?;
+    let content = std::fs::read_to_string(&full_path).with_path(&full_path)?;
     let file_lines: Vec<&str> = content.lines().collect();
     let line_count = file_lines.len() as u32;
     
@@ -368,32 +581,32 @@ pub fn git_diff_untracked(repo_path: &str, file_path: &str) -> Result<GitDiff, S
     Ok(GitDiff {
         path: file_path.to_string(),
         hunks,
+        lfs: None,
     })
 }
 
 #[tauri::command]
-pub fn git_stage_file(repo_path: &str, file_path: &str) -> Result<(), String> {
-    let repo = Repository::open(repo_path).map_err(|e| e.to_string())?;
-    let mut index = repo.index().map_err(|e| e.to_string())?;
-    index.add_path(std::path::Path::new(file_path)).map_err(|e| e.to_string())?;
-    index.write().map_err(|e| e.to_string())?;
+pub fn git_stage_file(repo_path: &str, file_path: &str) -> CommandResult<()> {
+    let repo = open_repo(repo_path)?;
+    let mut index = repo.index()?;
+    index.add_path(std::path::Path::new(file_path))?;
+    index.write()?;
     Ok(())
 }
 
 #[tauri::command]
-pub fn git_unstage_file(repo_path: &str, file_path: &str) -> Result<(), String> {
-    let repo = Repository::open(repo_path).map_err(|e| e.to_string())?;
+pub fn git_unstage_file(repo_path: &str, file_path: &str) -> CommandResult<()> {
+    let repo = open_repo(repo_path)?;
     
     match repo.head() {
         Ok(head) => {
-            let head_commit = head.peel_to_commit().map_err(|e| e.to_string())?;
-            repo.reset_default(Some(&head_commit.into_object()), &[std::path::Path::new(file_path)])
-                .map_err(|e| e.to_string())?;
+            let head_commit = head.peel_to_commit()?;
+            repo.reset_default(Some(&head_commit.into_object()), &[std::path::Path::new(file_path)])?;
         }
         Err(_) => {
-            let mut index = repo.index().map_err(|e| e.to_string())?;
-            index.remove_path(std::path::Path::new(file_path)).map_err(|e| e.to_string())?;
-            index.write().map_err(|e| e.to_string())?;
+            let mut index = repo.index()?;
+            index.remove_path(std::path::Path::new(file_path))?;
+            index.write()?;
         }
     }
     
@@ -401,32 +614,1239 @@ pub fn git_unstage_file(repo_path: &str, file_path: &str) -> Result<(), String>
 }
 
 #[tauri::command]
-pub fn git_show_file(repo_path: &str, file_path: &str, revision: &str) -> Result<String, String> {
-    let repo = Repository::open(repo_path).map_err(|e| e.to_string())?;
+pub fn git_show_file(repo_path: &str, file_path: &str, revision: &str) -> CommandResult<String> {
+    let repo = open_repo(repo_path)?;
     
-    let obj = repo.revparse_single(revision).map_err(|e| e.to_string())?;
-    let commit = obj.peel_to_commit().map_err(|e| e.to_string())?;
-    let tree = commit.tree().map_err(|e| e.to_string())?;
+    let obj = repo.revparse_single(revision)?;
+    let commit = obj.peel_to_commit()?;
+    let tree = commit.tree()?;
     
     let entry = tree.get_path(std::path::Path::new(file_path))
-        .map_err(|_| format!("File '{}' not found in {}", file_path, revision))?;
-    
-    let blob = repo.find_blob(entry.id()).map_err(|e| e.to_string())?;
+        .map_err(|_| {
+            CommandError::not_found(format!("File '{}' not found in {}", file_path, revision))
+                .with_path(file_path)
+        })?;
     
-    String::from_utf8(blob.content().to_vec())
-        .map_err(|_| "File contains non-UTF8 content".to_string())
+    let blob = repo.find_blob(entry.id())?;
+    show_blob_content(&repo, file_path, blob.content().to_vec())
 }
 
 #[tauri::command]
-pub fn git_show_staged_file(repo_path: &str, file_path: &str) -> Result<String, String> {
-    let repo = Repository::open(repo_path).map_err(|e| e.to_string())?;
-    let index = repo.index().map_err(|e| e.to_string())?;
+pub fn git_show_staged_file(repo_path: &str, file_path: &str) -> CommandResult<String> {
+    let repo = open_repo(repo_path)?;
+    let index = repo.index()?;
     
     let entry = index.get_path(std::path::Path::new(file_path), 0)
-        .ok_or_else(|| format!("File '{}' not found in staging area", file_path))?;
+        .ok_or_else(|| {
+            CommandError::not_found(format!("File '{}' not found in staging area", file_path))
+                .with_path(file_path)
+        })?;
     
-    let blob = repo.find_blob(entry.id).map_err(|e| e.to_string())?;
-    
-    String::from_utf8(blob.content().to_vec())
-        .map_err(|_| "File contains non-UTF8 content".to_string())
+    let blob = repo.find_blob(entry.id)?;
+    show_blob_content(&repo, file_path, blob.content().to_vec())
+}
+
+fn reflog_entries(repo: &Repository, ref_name: &str) -> CommandResult<Vec<GitReflogEntry>> {
+    let reflog = repo.reflog(ref_name)?;
+    let entries = reflog
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let committer = entry.committer();
+            GitReflogEntry {
+                ref_name: ref_name.to_string(),
+                index,
+                old_id: entry.id_old().to_string(),
+                new_id: entry.id_new().to_string(),
+                message: entry.message().unwrap_or("").to_string(),
+                committer: committer.name().unwrap_or("").to_string(),
+                time: committer.when().seconds(),
+            }
+        })
+        .collect();
+    Ok(entries)
+}
+
+#[tauri::command]
+pub fn git_reflog(repo_path: &str, ref_name: Option<String>) -> CommandResult<Vec<GitReflogEntry>> {
+    let repo = open_repo(repo_path)?;
+
+    if let Some(name) = ref_name {
+        return reflog_entries(&repo, &name);
+    }
+
+    let mut entries = reflog_entries(&repo, "HEAD")?;
+    let branches = repo.branches(Some(git2::BranchType::Local))?;
+    for branch in branches.flatten() {
+        if let Some(name) = branch.0.get().name() {
+            entries.extend(reflog_entries(&repo, name)?);
+        }
+    }
+
+    Ok(entries)
+}
+
+struct ReflogUndoPlan {
+    ref_name: String,
+    current: Oid,
+    target: Oid,
+    message: String,
+    updates_working_tree: bool,
+}
+
+fn plan_reflog_undo(repo: &Repository, ref_name: &str, index: usize) -> CommandResult<ReflogUndoPlan> {
+    let reference = repo
+        .find_reference(ref_name)
+        .map_err(|_| CommandError::not_found(format!("Reference '{}' not found", ref_name)))?;
+    let resolved = reference.resolve()?;
+    let current = resolved
+        .target()
+        .ok_or_else(|| CommandError::not_found(format!("Reference '{}' has no target", ref_name)))?;
+
+    let reflog = repo.reflog(ref_name)?;
+    let entry = reflog
+        .get(index)
+        .ok_or_else(|| {
+            CommandError::not_found(format!("Reflog entry {}@{{{}}} does not exist", ref_name, index))
+        })?;
+    let target = entry.id_new();
+    if target.is_zero() {
+        return Err(CommandError::not_found(format!("{}@{{{}}} does not point to a commit", ref_name, index)));
+    }
+    repo.find_commit(target)
+        .map_err(|_| {
+            CommandError::not_found(format!(
+                "Commit {} from {}@{{{}}} no longer exists",
+                target, ref_name, index
+            ))
+        })?;
+
+    let head = repo.head().ok();
+    let updates_working_tree = ref_name == "HEAD"
+        || head
+            .as_ref()
+            .map(|h| h.is_branch() && h.name() == resolved.name())
+            .unwrap_or(false);
+
+    Ok(ReflogUndoPlan {
+        ref_name: ref_name.to_string(),
+        current,
+        target,
+        message: entry.message().unwrap_or("").to_string(),
+        updates_working_tree,
+    })
+}
+
+fn blocking_worktree_changes(repo: &Repository, target: Oid) -> CommandResult<Vec<String>> {
+    let target_tree = repo
+        .find_commit(target)
+        .and_then(|c| c.tree())?;
+
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false);
+    let statuses = repo.statuses(Some(&mut opts))?;
+
+    let mut blocking = Vec::new();
+    for entry in statuses.iter() {
+        let path = match entry.path() {
+            Some(p) => p.to_string(),
+            None => continue,
+        };
+        let status = entry.status();
+        if status.is_wt_new() {
+            if target_tree.get_path(std::path::Path::new(&path)).is_ok() {
+                blocking.push(path);
+            }
+        } else {
+            blocking.push(path);
+        }
+    }
+
+    Ok(blocking)
+}
+
+fn changed_files_between(repo: &Repository, from: Oid, to: Oid) -> CommandResult<Vec<GitFileStatus>> {
+    let from_tree = repo
+        .find_commit(from)
+        .and_then(|c| c.tree())?;
+    let to_tree = repo
+        .find_commit(to)
+        .and_then(|c| c.tree())?;
+    let diff = repo.diff_tree_to_tree(Some(&from_tree), Some(&to_tree), None)?;
+
+    let files = diff
+        .deltas()
+        .filter_map(|delta| {
+            let path = delta
+                .new_file()
+                .path()
+                .or_else(|| delta.old_file().path())?
+                .to_string_lossy()
+                .to_string();
+            let status = match delta.status() {
+                git2::Delta::Added => "added",
+                git2::Delta::Deleted => "deleted",
+                git2::Delta::Renamed => "renamed",
+                _ => "modified",
+            };
+            Some(GitFileStatus {
+                lfs: is_lfs_tracked(repo, &path),
+                path,
+                status: status.to_string(),
+                staged: false,
+                working_tree: false,
+            })
+        })
+        .collect();
+
+    Ok(files)
+}
+
+#[tauri::command]
+pub fn git_reflog_undo_preview(
+    repo_path: &str,
+    ref_name: &str,
+    index: usize,
+) -> CommandResult<GitReflogUndoPreview> {
+    let repo = open_repo(repo_path)?;
+    let plan = plan_reflog_undo(&repo, ref_name, index)?;
+
+    let files = changed_files_between(&repo, plan.current, plan.target)?;
+    let blocking_files = if plan.updates_working_tree {
+        blocking_worktree_changes(&repo, plan.target)?
+    } else {
+        Vec::new()
+    };
+
+    let discarded_commits = if repo.graph_descendant_of(plan.target, plan.current).unwrap_or(false) {
+        0
+    } else {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(plan.current)?;
+        revwalk.hide(plan.target)?;
+        revwalk.count()
+    };
+
+    Ok(GitReflogUndoPreview {
+        ref_name: plan.ref_name,
+        current_id: plan.current.to_string(),
+        target_id: plan.target.to_string(),
+        message: plan.message,
+        updates_working_tree: plan.updates_working_tree,
+        discarded_commits,
+        files,
+        blocking_files,
+    })
+}
+
+#[tauri::command]
+pub fn git_reflog_undo(repo_path: &str, ref_name: &str, index: usize) -> CommandResult<()> {
+    let repo = open_repo(repo_path)?;
+    let plan = plan_reflog_undo(&repo, ref_name, index)?;
+
+    if plan.current == plan.target {
+        return Ok(());
+    }
+
+    if plan.updates_working_tree {
+        let blocking = blocking_worktree_changes(&repo, plan.target)?;
+        if !blocking.is_empty() {
+            return Err(CommandError::conflict(format!(
+                "Undo would discard uncommitted changes in: {}. Commit or stash them first.",
+                blocking.join(", ")
+            )));
+        }
+
+        let target = repo.find_object(plan.target, None)?;
+        let mut checkout_builder = git2::build::CheckoutBuilder::new();
+        checkout_builder.force();
+        repo.reset(&target, ResetType::Hard, Some(&mut checkout_builder))?;
+    } else {
+        let message = format!("litcode: undo to {}@{{{}}}", plan.ref_name, index);
+        repo.reference(&plan.ref_name, plan.target, true, &message)?;
+    }
+
+    Ok(())
+}
+
+fn temp_file_path(prefix: &str) -> std::path::PathBuf {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64;
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    std::env::temp_dir().join(format!(
+        "litcode-{}-{}-{:x}-{:x}",
+        prefix,
+        std::process::id(),
+        timestamp,
+        count
+    ))
+}
+
+fn write_temp_file(prefix: &str, content: &[u8]) -> CommandResult<std::path::PathBuf> {
+    use std::io::Write;
+
+    let path = temp_file_path(prefix);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(&path).with_path(&path)?;
+    if let Err(e) = file.write_all(content) {
+        let _ = std::fs::remove_file(&path);
+        return Err(CommandError::from(e).with_path(&path));
+    }
+    Ok(path)
+}
+
+fn run_with_stdin(
+    program: &str,
+    args: &[&str],
+    input: &[u8],
+) -> CommandResult<std::process::Output> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| CommandError::from(e).with_path(program))?;
+
+    let stdin = child.stdin.take();
+    std::thread::scope(|scope| {
+        let writer = scope.spawn(move || match stdin {
+            Some(mut stdin) => stdin.write_all(input),
+            None => Ok(()),
+        });
+        let output = child.wait_with_output().with_path(program)?;
+        match writer.join() {
+            Ok(Err(e)) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+                Err(CommandError::from(e).with_path(program))
+            }
+            _ => Ok(output),
+        }
+    })
+}
+
+fn signing_format(config: &git2::Config) -> String {
+    config
+        .get_string("gpg.format")
+        .unwrap_or_else(|_| "openpgp".to_string())
+}
+
+fn signing_program(config: &git2::Config, format: &str) -> String {
+    let keys: &[&str] = match format {
+        "ssh" => &["gpg.ssh.program"],
+        "x509" => &["gpg.x509.program"],
+        _ => &["gpg.openpgp.program", "gpg.program"],
+    };
+    for key in keys {
+        if let Ok(program) = config.get_string(key) {
+            return program;
+        }
+    }
+    match format {
+        "ssh" => "ssh-keygen".to_string(),
+        "x509" => "gpgsm".to_string(),
+        _ => "gpg".to_string(),
+    }
+}
+
+fn sign_commit_buffer(
+    config: &git2::Config,
+    committer: &git2::Signature,
+    buffer: &str,
+) -> CommandResult<String> {
+    let format = signing_format(config);
+    let program = signing_program(config, &format);
+    let signing_key = config.get_string("user.signingkey").ok();
+
+    let output = if format == "ssh" {
+        let key = signing_key
+            .ok_or_else(|| {
+                CommandError::invalid_input(
+                    "user.signingkey must be set to sign commits with gpg.format=ssh",
+                )
+            })?;
+        let literal_key = key.strip_prefix("key::").map(|k| k.to_string()).or_else(|| {
+            if key.starts_with("ssh-") || key.starts_with("ecdsa-") || key.starts_with("sk-") {
+                Some(key.clone())
+            } else {
+                None
+            }
+        });
+
+        match literal_key {
+            Some(public_key) => {
+                let key_file = write_temp_file("signing-key", public_key.as_bytes())?;
+                let key_file_str = key_file.to_string_lossy().to_string();
+                let output = run_with_stdin(
+                    &program,
+                    &["-Y", "sign", "-n", "git", "-U", "-f", &key_file_str],
+                    buffer.as_bytes(),
+                );
+                let _ = std::fs::remove_file(&key_file);
+                output?
+            }
+            None => {
+                let key_path = match key.strip_prefix("~/") {
+                    Some(rest) => format!("{}/{}", std::env::var("HOME").unwrap_or_default(), rest),
+                    None => key,
+                };
+                run_with_stdin(
+                    &program,
+                    &["-Y", "sign", "-n", "git", "-f", &key_path],
+                    buffer.as_bytes(),
+                )?
+            }
+        }
+    } else {
+        let key = signing_key.unwrap_or_else(|| committer.to_string());
+        run_with_stdin(&program, &["--status-fd=2", "-bsau", &key], buffer.as_bytes())?
+    };
+
+    if !output.status.success() {
+        return Err(CommandError::other(format!(
+            "Failed to sign commit with {}: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let signature = String::from_utf8(output.stdout)
+        .map_err(|_| CommandError::other(format!("{} produced a non-UTF8 signature", program)))?;
+    if signature.trim().is_empty() {
+        return Err(CommandError::other(format!("{} produced an empty signature", program)));
+    }
+
+    Ok(signature)
+}
+
+fn verify_commit_signature(repo: &Repository, commit_id: Oid) -> GitSignatureStatus {
+    let (signature, signed_data) = match repo.extract_signature(&commit_id, None) {
+        Ok(parts) => parts,
+        Err(_) => {
+            return GitSignatureStatus {
+                status: "unsigned".to_string(),
+                format: None,
+                signer: None,
+            }
+        }
+    };
+
+    let signature = signature.to_vec();
+    let signed_data = signed_data.to_vec();
+    let format = if signature.starts_with(b"-----BEGIN SSH SIGNATURE-----") {
+        "ssh"
+    } else if signature.starts_with(b"-----BEGIN SIGNED MESSAGE-----") {
+        "x509"
+    } else {
+        "openpgp"
+    };
+
+    let unknown = |signer: Option<String>| GitSignatureStatus {
+        status: "unknown".to_string(),
+        format: Some(format.to_string()),
+        signer,
+    };
+
+    let config = match repo.config() {
+        Ok(c) => c,
+        Err(_) => return unknown(None),
+    };
+    let program = signing_program(&config, format);
+
+    let sig_file = match write_temp_file("signature", &signature) {
+        Ok(path) => path,
+        Err(_) => return unknown(None),
+    };
+    let sig_file_str = sig_file.to_string_lossy().to_string();
+
+    let result = if format == "ssh" {
+        verify_ssh_signature(&config, &program, &sig_file_str, &signed_data)
+    } else {
+        verify_gpg_signature(&program, &sig_file_str, &signed_data)
+    };
+    let _ = std::fs::remove_file(&sig_file);
+
+    match result {
+        Some((status, signer)) => GitSignatureStatus {
+            status: status.to_string(),
+            format: Some(format.to_string()),
+            signer,
+        },
+        None => unknown(None),
+    }
+}
+
+fn verify_gpg_signature(
+    program: &str,
+    sig_file: &str,
+    signed_data: &[u8],
+) -> Option<(&'static str, Option<String>)> {
+    let output = run_with_stdin(program, &["--status-fd=1", "--verify", sig_file, "-"], signed_data).ok()?;
+    Some(parse_gpg_status(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_gpg_status(status_text: &str) -> (&'static str, Option<String>) {
+    let mut status = None;
+    let mut signer = None;
+    let mut trusted = false;
+    for line in status_text.lines() {
+        let line = match line.strip_prefix("[GNUPG:] ") {
+            Some(l) => l,
+            None => continue,
+        };
+        let mut parts = line.splitn(3, ' ');
+        let keyword = parts.next().unwrap_or("");
+        let _key_id = parts.next();
+        let user = parts.next().map(|u| u.to_string());
+        match keyword {
+            "GOODSIG" => {
+                status = status.or(Some("good"));
+                signer = user;
+            }
+            "BADSIG" => {
+                status = Some("bad");
+                signer = user;
+            }
+            "EXPSIG" | "EXPKEYSIG" | "REVKEYSIG" => {
+                status = Some("expired");
+                signer = user;
+            }
+            "ERRSIG" | "NO_PUBKEY" => status = status.or(Some("unknown")),
+            "TRUST_MARGINAL" | "TRUST_FULLY" | "TRUST_ULTIMATE" => trusted = true,
+            _ => {}
+        }
+    }
+
+    match status {
+        Some("good") if !trusted => ("untrusted", signer),
+        status => (status.unwrap_or("unknown"), signer),
+    }
+}
+
+fn verify_ssh_signature(
+    config: &git2::Config,
+    program: &str,
+    sig_file: &str,
+    signed_data: &[u8],
+) -> Option<(&'static str, Option<String>)> {
+    let allowed_signers = config.get_path("gpg.ssh.allowedSignersFile").ok()?;
+    let allowed_signers = allowed_signers.to_string_lossy().to_string();
+
+    let principals = run_with_stdin(
+        program,
+        &["-Y", "find-principals", "-f", &allowed_signers, "-s", sig_file],
+        &[],
+    )
+    .ok()?;
+    let principal = String::from_utf8_lossy(&principals.stdout)
+        .lines()
+        .next()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty());
+
+    let principal = match principal {
+        Some(p) => p,
+        None => return Some(("unknown", None)),
+    };
+
+    let output = run_with_stdin(
+        program,
+        &["-Y", "verify", "-n", "git", "-f", &allowed_signers, "-I", &principal, "-s", sig_file],
+        signed_data,
+    )
+    .ok()?;
+
+    let status = if output.status.success() { "good" } else { "bad" };
+    Some((status, Some(principal)))
+}
+
+#[tauri::command]
+pub fn git_commit(repo_path: &str, message: &str) -> CommandResult<String> {
+    let mut repo = open_repo(repo_path)?;
+    let mut merge_heads = Vec::new();
+    if repo.state() == git2::RepositoryState::Merge {
+        repo.mergehead_foreach(|oid| {
+            merge_heads.push(*oid);
+            true
+        })?;
+    }
+    let config = repo.config()?;
+    let committer = repo
+        .signature()
+        .map_err(|_| CommandError::invalid_input("Set user.name and user.email before committing"))?;
+
+    let mut index = repo.index()?;
+    let tree_id = index.write_tree()?;
+    let tree = repo.find_tree(tree_id)?;
+
+    let mut parent_commits = Vec::new();
+    if let Ok(head) = repo.head() {
+        parent_commits.push(head.peel_to_commit()?);
+    }
+    for oid in &merge_heads {
+        parent_commits.push(repo.find_commit(*oid)?);
+    }
+    let parents: Vec<&git2::Commit> = parent_commits.iter().collect();
+
+    let summary = message.lines().next().unwrap_or("");
+    let reflog_message = if parents.is_empty() {
+        format!("commit (initial): {}", summary)
+    } else if !merge_heads.is_empty() {
+        format!("commit (merge): {}", summary)
+    } else {
+        format!("commit: {}", summary)
+    };
+
+    let gpg_sign = config.get_bool("commit.gpgsign").unwrap_or(false);
+    if !gpg_sign {
+        let oid = repo.commit(Some("HEAD"), &committer, &committer, message, &tree, &parents)?;
+        repo.cleanup_state()?;
+        return Ok(oid.to_string());
+    }
+
+    let buffer = repo.commit_create_buffer(&committer, &committer, message, &tree, &parents)?;
+    let buffer = buffer
+        .as_str()
+        .ok_or_else(|| CommandError::other("Commit buffer is not valid UTF-8"))?
+        .to_string();
+    let signature = sign_commit_buffer(&config, &committer, &buffer)?;
+    let oid = repo.commit_signed(&buffer, &signature, Some("gpgsig"))?;
+
+    let head_ref = repo.find_reference("HEAD")?;
+    match head_ref.symbolic_target() {
+        Some(branch) => {
+            repo.reference(branch, oid, true, &reflog_message)?;
+        }
+        None => {
+            repo.set_head_detached(oid)?;
+        }
+    }
+    repo.cleanup_state()?;
+
+    Ok(oid.to_string())
+}
+
+#[tauri::command]
+pub fn git_log(
+    repo_path: &str,
+    revision: Option<String>,
+    limit: Option<usize>,
+) -> CommandResult<Vec<GitCommit>> {
+    let repo = open_repo(repo_path)?;
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk
+        .set_sorting(git2::Sort::TIME)?;
+    match revision {
+        Some(rev) => {
+            let obj = repo.revparse_single(&rev)?;
+            let commit = obj.peel_to_commit()?;
+            revwalk.push(commit.id())?;
+        }
+        None => {
+            if repo.head().is_err() {
+                return Ok(vec![]);
+            }
+            revwalk.push_head()?;
+        }
+    }
+
+    let mut commits = Vec::new();
+    for oid in revwalk.take(limit.unwrap_or(100)) {
+        let oid = oid?;
+        let commit = repo.find_commit(oid)?;
+        let author = commit.author();
+
+        commits.push(GitCommit {
+            id: oid.to_string(),
+            summary: commit.summary().unwrap_or("").to_string(),
+            message: commit.message().unwrap_or("").to_string(),
+            author: author.name().unwrap_or("").to_string(),
+            email: author.email().unwrap_or("").to_string(),
+            time: author.when().seconds(),
+            parents: commit.parent_ids().map(|id| id.to_string()).collect(),
+            signature: signature_presence(&repo, oid),
+        });
+    }
+
+    Ok(commits)
+}
+
+fn signature_presence(repo: &Repository, commit_id: Oid) -> GitSignatureStatus {
+    let format = match repo.extract_signature(&commit_id, None) {
+        Ok((signature, _)) if signature.starts_with(b"-----BEGIN SSH SIGNATURE-----") => "ssh",
+        Ok((signature, _)) if signature.starts_with(b"-----BEGIN SIGNED MESSAGE-----") => "x509",
+        Ok(_) => "openpgp",
+        Err(_) => {
+            return GitSignatureStatus {
+                status: "unsigned".to_string(),
+                format: None,
+                signer: None,
+            }
+        }
+    };
+    GitSignatureStatus {
+        status: "unverified".to_string(),
+        format: Some(format.to_string()),
+        signer: None,
+    }
+}
+
+#[tauri::command]
+pub async fn git_verify_signatures(
+    repo_path: String,
+    commit_ids: Vec<String>,
+) -> CommandResult<Vec<GitSignatureStatus>> {
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repo(&repo_path)?;
+        commit_ids
+            .iter()
+            .map(|id| Ok(verify_commit_signature(&repo, Oid::from_str(id)?)))
+            .collect()
+    })
+    .await
+    .map_err(|e| CommandError::other(format!("Signature verification failed: {}", e)))?
+}
+
+const LFS_POINTER_MAX_SIZE: usize = 1024;
+const LFS_POINTER_VERSION: &str = "version https://git-lfs.github.com/spec/v1";
+
+fn is_lfs_tracked(repo: &Repository, file_path: &str) -> bool {
+    matches!(
+        repo.get_attr(
+            std::path::Path::new(file_path),
+            "filter",
+            git2::AttrCheckFlags::FILE_THEN_INDEX,
+        ),
+        Ok(Some("lfs"))
+    )
+}
+
+/// Paths whose only change is the executable bit. Workdir content goes through
+/// the diff machinery so autocrlf and clean filters (LFS) are applied.
+#[derive(Default)]
+pub(crate) struct ModeChanges {
+    staged: std::collections::HashSet<String>,
+    unstaged: std::collections::HashSet<String>,
+}
+
+impl ModeChanges {
+    pub(crate) fn load<'a>(repo: &Repository, statuses: impl Iterator<Item = (&'a str, git2::Status)>) -> Self {
+        let mut staged = Vec::new();
+        let mut unstaged = Vec::new();
+        for (path, status) in statuses {
+            if status.is_index_modified() {
+                staged.push(path);
+            }
+            if status.is_wt_modified() {
+                unstaged.push(path);
+            }
+        }
+
+        let mut changes = ModeChanges::default();
+        if !staged.is_empty() {
+            let head_tree = repo.head().and_then(|h| h.peel_to_tree()).ok();
+            if let Ok(diff) = repo.diff_tree_to_index(head_tree.as_ref(), None, Some(&mut mode_diff_options(&staged))) {
+                changes.staged = mode_only_paths(&diff, false);
+            }
+        }
+        if !unstaged.is_empty() {
+            if let Ok(diff) = repo.diff_index_to_workdir(None, Some(&mut mode_diff_options(&unstaged))) {
+                changes.unstaged = mode_only_paths(&diff, true);
+            }
+        }
+        changes
+    }
+
+    pub(crate) fn is_mode_only(&self, path: &str, staged: bool) -> bool {
+        if staged {
+            self.staged.contains(path)
+        } else {
+            self.unstaged.contains(path)
+        }
+    }
+}
+
+fn mode_diff_options(paths: &[&str]) -> DiffOptions {
+    let mut opts = DiffOptions::new();
+    opts.include_typechange(true).disable_pathspec_match(true);
+    for path in paths {
+        opts.pathspec(path);
+    }
+    opts
+}
+
+fn mode_only_paths(diff: &git2::Diff, compare_content: bool) -> std::collections::HashSet<String> {
+    diff.deltas()
+        .enumerate()
+        .filter(|(_, delta)| {
+            delta.status() == git2::Delta::Modified && delta.old_file().mode() != delta.new_file().mode()
+        })
+        .filter(|(index, delta)| {
+            if !compare_content || !delta.new_file().id().is_zero() {
+                return delta.old_file().id() == delta.new_file().id();
+            }
+            match git2::Patch::from_diff(diff, *index) {
+                Ok(Some(patch)) => !patch.delta().flags().is_binary() && patch.num_hunks() == 0,
+                _ => false,
+            }
+        })
+        .filter_map(|(_, delta)| delta.new_file().path().map(|p| p.to_string_lossy().replace('\\', "/")))
+        .collect()
+}
+
+fn parse_lfs_pointer(content: &[u8]) -> Option<(String, u64)> {
+    if content.len() > LFS_POINTER_MAX_SIZE {
+        return None;
+    }
+    let text = std::str::from_utf8(content).ok()?;
+    let mut lines = text.lines();
+    if lines.next()? != LFS_POINTER_VERSION {
+        return None;
+    }
+
+    let mut oid = None;
+    let mut size = None;
+    for line in lines {
+        if let Some(value) = line.strip_prefix("oid sha256:") {
+            if value.len() == 64 && value.bytes().all(|b| b.is_ascii_hexdigit()) {
+                oid = Some(value.to_lowercase());
+            }
+        } else if let Some(value) = line.strip_prefix("size ") {
+            size = value.parse::<u64>().ok();
+        }
+    }
+
+    Some((oid?, size?))
+}
+
+fn lfs_object_path(repo: &Repository, oid: &str) -> std::path::PathBuf {
+    repo.path()
+        .join("lfs")
+        .join("objects")
+        .join(&oid[0..2])
+        .join(&oid[2..4])
+        .join(oid)
+}
+
+fn lfs_pointer_info(repo: &Repository, file_path: &str, content: &[u8]) -> Option<GitLfsPointer> {
+    if !is_lfs_tracked(repo, file_path) {
+        return None;
+    }
+    let (oid, size) = parse_lfs_pointer(content)?;
+    let available = std::fs::metadata(lfs_object_path(repo, &oid))
+        .map(|m| m.is_file() && m.len() == size)
+        .unwrap_or(false);
+    Some(GitLfsPointer { oid, size, available })
+}
+
+fn resolve_lfs_content(
+    repo: &Repository,
+    file_path: &str,
+    content: Vec<u8>,
+) -> (Vec<u8>, Option<GitLfsPointer>) {
+    let pointer = match lfs_pointer_info(repo, file_path, &content) {
+        Some(p) => p,
+        None => return (content, None),
+    };
+    if pointer.available {
+        if let Ok(object) = std::fs::read(lfs_object_path(repo, &pointer.oid)) {
+            return (object, Some(pointer));
+        }
+    }
+    (content, Some(pointer))
+}
+
+fn show_blob_content(repo: &Repository, file_path: &str, content: Vec<u8>) -> CommandResult<String> {
+    let (content, pointer) = resolve_lfs_content(repo, file_path, content);
+    match (String::from_utf8(content), pointer) {
+        (Ok(text), _) => Ok(text),
+        (Err(_), Some(pointer)) => Ok(format!(
+            "{}\noid sha256:{}\nsize {}\n",
+            LFS_POINTER_VERSION, pointer.oid, pointer.size
+        )),
+        (Err(_), None) => Err(
+            CommandError::new(ErrorKind::Unsupported, "File contains non-UTF8 content").with_path(file_path),
+        ),
+    }
+}
+
+fn head_blob_content(repo: &Repository, file_path: &str) -> Option<Vec<u8>> {
+    let tree = repo.head().ok()?.peel_to_tree().ok()?;
+    let entry = tree.get_path(std::path::Path::new(file_path)).ok()?;
+    let blob = repo.find_blob(entry.id()).ok()?;
+    Some(blob.content().to_vec())
+}
+
+fn index_blob_content(repo: &Repository, file_path: &str) -> Option<Vec<u8>> {
+    let index = repo.index().ok()?;
+    let entry = index.get_path(std::path::Path::new(file_path), 0)?;
+    let blob = repo.find_blob(entry.id).ok()?;
+    Some(blob.content().to_vec())
+}
+
+pub(crate) fn diff_buffers(
+    old_content: &[u8],
+    new_content: &[u8],
+    file_path: &str,
+    opts: Option<&mut DiffOptions>,
+) -> CommandResult<Vec<GitHunk>> {
+    let mut collector = HunkCollector::default();
+    if old_content != new_content {
+        let mut patch = git2::Patch::from_buffers(
+            old_content,
+            Some(std::path::Path::new(file_path)),
+            new_content,
+            Some(std::path::Path::new(file_path)),
+            opts,
+        )?;
+        patch
+            .print(&mut |_delta, hunk, line| {
+                collector.push(hunk, line);
+                true
+            })?;
+    }
+    Ok(collector.finish())
+}
+
+pub(crate) fn diff_files(diff: &git2::Diff) -> CommandResult<Vec<GitDiff>> {
+    let mut files = Vec::new();
+    for index in 0..diff.deltas().len() {
+        let path = match diff.get_delta(index).and_then(|delta| {
+            delta
+                .new_file()
+                .path()
+                .or_else(|| delta.old_file().path())
+                .map(|p| p.to_string_lossy().to_string())
+        }) {
+            Some(path) => path,
+            None => continue,
+        };
+
+        let mut collector = HunkCollector::default();
+        if let Some(mut patch) = git2::Patch::from_diff(diff, index)? {
+            patch.print(&mut |_delta, hunk, line| {
+                collector.push(hunk, line);
+                true
+            })?;
+        }
+        files.push(GitDiff {
+            path,
+            hunks: collector.finish(),
+            lfs: None,
+        });
+    }
+    Ok(files)
+}
+
+fn lfs_diff(repo: &Repository, file_path: &str, staged: bool) -> CommandResult<GitDiff> {
+    let (old_content, new_content) = if staged {
+        (
+            head_blob_content(repo, file_path),
+            index_blob_content(repo, file_path),
+        )
+    } else {
+        let workdir = repo
+            .workdir()
+            .ok_or_else(|| CommandError::new(ErrorKind::Unsupported, "Repository has no working directory"))?;
+        (
+            index_blob_content(repo, file_path),
+            std::fs::read(ensure_allowed(workdir.join(file_path))?).ok(),
+        )
+    };
+
+    let (old_content, old_pointer) = match old_content {
+        Some(c) => resolve_lfs_content(repo, file_path, c),
+        None => (Vec::new(), None),
+    };
+    let (new_content, new_pointer) = match new_content {
+        Some(c) => resolve_lfs_content(repo, file_path, c),
+        None => (Vec::new(), None),
+    };
+
+    Ok(GitDiff {
+        path: file_path.to_string(),
+        hunks: diff_buffers(&old_content, &new_content, file_path, None)?,
+        lfs: Some(GitLfsDiff {
+            old: old_pointer,
+            new: new_pointer,
+        }),
+    })
+}
+
+#[tauri::command]
+pub fn git_lfs_pointer(
+    repo_path: &str,
+    file_path: &str,
+    revision: Option<String>,
+) -> CommandResult<Option<GitLfsPointer>> {
+    let repo = open_repo(repo_path)?;
+
+    let content = match revision {
+        Some(rev) => {
+            let obj = repo.revparse_single(&rev)?;
+            let tree = obj.peel_to_tree()?;
+            let entry = tree
+                .get_path(std::path::Path::new(file_path))
+                .map_err(|_| {
+                    CommandError::not_found(format!("File '{}' not found in {}", file_path, rev))
+                        .with_path(file_path)
+                })?;
+            let blob = repo.find_blob(entry.id())?;
+            blob.content().to_vec()
+        }
+        None => index_blob_content(&repo, file_path)
+            .ok_or_else(|| {
+                CommandError::not_found(format!("File '{}' not found in staging area", file_path))
+                    .with_path(file_path)
+            })?,
+    };
+
+    Ok(lfs_pointer_info(&repo, file_path, &content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixture {
+        dir: std::path::PathBuf,
+    }
+
+    impl Fixture {
+        fn new(files: &[(&str, &str)]) -> Self {
+            let dir = temp_file_path("git-test");
+            std::fs::create_dir_all(&dir).unwrap();
+            let repo = Repository::init(&dir).unwrap();
+
+            crate::commands::workspace::trust_root(&dir).unwrap();
+            let fixture = Fixture { dir };
+            for (name, content) in files {
+                fixture.write(name, content);
+            }
+
+            let mut index = repo.index().unwrap();
+            index
+                .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+                .unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+            repo.commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[])
+                .unwrap();
+
+            fixture
+        }
+
+        fn repo_path(&self) -> &str {
+            self.dir.to_str().unwrap()
+        }
+
+        fn write(&self, name: &str, content: &str) {
+            let path = self.dir.join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+
+        fn read(&self, name: &str) -> String {
+            std::fs::read_to_string(self.dir.join(name)).unwrap()
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    fn revert_first_hunk(original: &str, modified: &str) -> String {
+        let fixture = Fixture::new(&[("file.txt", original)]);
+        fixture.write("file.txt", modified);
+        git_revert_hunk(fixture.repo_path(), "file.txt", 0).unwrap();
+        fixture.read("file.txt")
+    }
+
+    #[test]
+    fn diff_groups_lines_by_hunk() {
+        let hunks = diff_buffers(b"a\nb\nc\nd\n", b"a\nB\nC\nd\n", "file.txt", None).unwrap();
+        assert_eq!(hunks.len(), 1);
+        let types: Vec<&str> = hunks[0].lines.iter().map(|l| l.line_type.as_str()).collect();
+        assert_eq!(types, ["context", "delete", "delete", "add", "add", "context"]);
+    }
+
+    #[test]
+    fn revert_hunk_keeps_crlf_endings() {
+        let original = "one\r\ntwo\r\nthree\r\n";
+        assert_eq!(revert_first_hunk(original, "one\r\nTWO\r\nthree\r\n"), original);
+    }
+
+    #[test]
+    fn revert_hunk_keeps_trailing_whitespace() {
+        let original = "fn main() {  \n\tlet x = 1;\t\n}\n";
+        let modified = "fn main() {  \n\tlet x = 2;\n}\n";
+        assert_eq!(revert_first_hunk(original, modified), original);
+    }
+
+    #[test]
+    fn revert_hunk_without_final_newline() {
+        let original = "alpha\nbeta";
+        assert_eq!(revert_first_hunk(original, "alpha\nBETA"), original);
+        assert_eq!(revert_first_hunk(original, "alpha\nbeta\ngamma\n"), original);
+    }
+
+    #[test]
+    fn revert_hunk_restores_missing_final_newline() {
+        let original = "alpha\nbeta\n";
+        assert_eq!(revert_first_hunk(original, "alpha\nbeta"), original);
+    }
+
+    #[test]
+    fn revert_hunk_with_mixed_endings() {
+        let original = "one\r\ntwo\nthree\r\nfour\n";
+        let modified = "one\r\n2\nthree\r\nfour\n";
+        assert_eq!(revert_first_hunk(original, modified), original);
+    }
+
+    #[test]
+    fn revert_hunk_restores_deleted_lines() {
+        let original = "a\nb\nc\n";
+        assert_eq!(revert_first_hunk(original, "a\nc\n"), original);
+        assert_eq!(revert_first_hunk(original, ""), original);
+    }
+
+    #[test]
+    fn revert_hunk_applies_eol_attribute() {
+        let fixture = Fixture::new(&[
+            (".gitattributes", "*.txt text eol=crlf\n"),
+            ("file.txt", "one\r\ntwo\r\nthree\r\n"),
+        ]);
+        fixture.write("file.txt", "one\r\nthree\r\n");
+        git_revert_hunk(fixture.repo_path(), "file.txt", 0).unwrap();
+        assert_eq!(fixture.read("file.txt"), "one\r\ntwo\r\nthree\r\n");
+    }
+
+    #[test]
+    fn revert_lines_keeps_untouched_lines_exact() {
+        let fixture = Fixture::new(&[("file.txt", "keep \r\nold\nend")]);
+        fixture.write("file.txt", "keep \r\nnew\nend");
+        git_revert_lines(fixture.repo_path(), "file.txt", 2, 2).unwrap();
+        assert_eq!(fixture.read("file.txt"), "keep \r\nold\nend");
+    }
+
+    #[test]
+    fn revert_lines_restores_deletion_at_new_position() {
+        let fixture = Fixture::new(&[("file.txt", "1\n2\n3\n4\n")]);
+        fixture.write("file.txt", "0\n1\n2\n4\n");
+        git_revert_lines(fixture.repo_path(), "file.txt", 3, 3).unwrap();
+        assert_eq!(fixture.read("file.txt"), "0\n1\n2\n3\n4\n");
+    }
+
+    #[test]
+    fn revert_lines_crlf_without_final_newline() {
+        let fixture = Fixture::new(&[("file.txt", "a\r\nb\r\nc")]);
+        fixture.write("file.txt", "a\r\nb\r\nc\r\nd");
+        git_revert_lines(fixture.repo_path(), "file.txt", 4, 4).unwrap();
+        assert_eq!(fixture.read("file.txt"), "a\r\nb\r\nc\r\n");
+    }
+
+    #[test]
+    fn revert_lines_keeps_bom_and_records_history() {
+        let fixture = Fixture::new(&[("file.txt", "\u{feff}one\ntwo\n")]);
+        fixture.write("file.txt", "\u{feff}one\nTWO\n");
+        git_revert_lines(fixture.repo_path(), "file.txt", 2, 2).unwrap();
+        assert_eq!(fixture.read("file.txt"), "\u{feff}one\ntwo\n");
+
+        let path = fixture.dir.join("file.txt");
+        let snapshots = history::history_list(path.to_str().unwrap()).unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].source, "revert");
+    }
+
+    #[test]
+    fn gpg_status_requires_trusted_key() {
+        let good = "[GNUPG:] GOODSIG ABCD Alice <a@example.com>\n[GNUPG:] TRUST_FULLY 0 pgp\n";
+        assert_eq!(parse_gpg_status(good), ("good", Some("Alice <a@example.com>".to_string())));
+        let undefined = "[GNUPG:] GOODSIG ABCD Alice\n[GNUPG:] TRUST_UNDEFINED 0 pgp\n";
+        assert_eq!(parse_gpg_status(undefined).0, "untrusted");
+        assert_eq!(parse_gpg_status("[GNUPG:] GOODSIG ABCD Alice\n").0, "untrusted");
+        assert_eq!(parse_gpg_status("[GNUPG:] BADSIG ABCD Alice\n").0, "bad");
+        assert_eq!(parse_gpg_status("[GNUPG:] ERRSIG ABCD 1 8 00 0 9\n").0, "unknown");
+    }
+
+    #[test]
+    fn temp_files_are_created_fresh() {
+        let first = write_temp_file("test", b"one").unwrap();
+        let second = write_temp_file("test", b"two").unwrap();
+        assert_ne!(first, second);
+        assert_eq!(std::fs::read(&first).unwrap(), b"one");
+        std::fs::remove_file(first).unwrap();
+        std::fs::remove_file(second).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn signed_merge_commit_keeps_merge_head_parent() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let fixture = Fixture::new(&[("file.txt", "base\n")]);
+        let repo = Repository::open(fixture.repo_path()).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let author = git2::Signature::now("Test", "test@example.com").unwrap();
+        let other = repo
+            .commit(None, &author, &author, "other", &head.tree().unwrap(), &[&head])
+            .unwrap();
+        std::fs::write(repo.path().join("MERGE_HEAD"), format!("{}\n", other)).unwrap();
+
+        let program = repo.path().join("fake-gpg");
+        std::fs::write(
+            &program,
+            "#!/bin/sh\ncat > /dev/null\necho '-----BEGIN PGP SIGNATURE-----'\necho '-----END PGP SIGNATURE-----'\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+        config.set_str("gpg.program", program.to_str().unwrap()).unwrap();
+
+        let id = git_commit(fixture.repo_path(), "merge").unwrap();
+        let commit = repo.find_commit(Oid::from_str(&id).unwrap()).unwrap();
+        assert_eq!(commit.parent_ids().collect::<Vec<_>>(), vec![head.id(), other]);
+        assert!(repo.extract_signature(&commit.id(), None).is_ok());
+        assert_eq!(repo.head().unwrap().target(), Some(commit.id()));
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+    }
+
+    #[test]
+    fn show_file_returns_pointer_for_binary_lfs_object() {
+        let oid = "4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393";
+        let pointer = format!("{}\noid sha256:{}\nsize 4\n", LFS_POINTER_VERSION, oid);
+        let fixture = Fixture::new(&[
+            (".gitattributes", "*.bin filter=lfs diff=lfs merge=lfs -text\n"),
+            ("image.bin", &pointer),
+        ]);
+        let repo = Repository::open(fixture.repo_path()).unwrap();
+        let object = lfs_object_path(&repo, oid);
+        std::fs::create_dir_all(object.parent().unwrap()).unwrap();
+        std::fs::write(&object, [0x89, 0xff, 0x00, 0xfe]).unwrap();
+
+        assert_eq!(git_show_file(fixture.repo_path(), "image.bin", "HEAD").unwrap(), pointer);
+        assert_eq!(git_show_staged_file(fixture.repo_path(), "image.bin").unwrap(), pointer);
+    }
 }
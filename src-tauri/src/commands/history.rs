@@ -0,0 +1,374 @@
+use super::config::litcode_dir;
+use super::error::{CommandError, CommandResult, ResultExt};
+use super::fs::{atomic_write, FileVersion};
+use super::git::{diff_buffers, GitDiff};
+use super::workspace::ensure_allowed;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MAX_SNAPSHOTS_PER_FILE: usize = 50;
+const MAX_SNAPSHOT_AGE_MS: u64 = 30 * 24 * 60 * 60 * 1000;
+const MAX_SNAPSHOT_SIZE: usize = 5 * 1024 * 1024;
+const MAX_DELETED_DIR_FILES: usize = 2000;
+const MAX_DELETED_DIR_BYTES: u64 = 64 * 1024 * 1024;
+
+static REFERENCES: Mutex<Option<HashMap<String, usize>>> = Mutex::new(None);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HistorySnapshot {
+    pub id: String,
+    pub path: String,
+    pub timestamp: u64,
+    pub size: u64,
+    pub hash: String,
+    pub source: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct HistoryIndex {
+    path: String,
+    snapshots: Vec<HistorySnapshot>,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn hash_bytes(bytes: &[u8]) -> CommandResult<String> {
+    Ok(git2::Oid::hash_object(git2::ObjectType::Blob, bytes)?.to_string())
+}
+
+fn history_dir() -> CommandResult<PathBuf> {
+    Ok(litcode_dir()?.join("history"))
+}
+
+fn object_path(hash: &str) -> CommandResult<PathBuf> {
+    Ok(history_dir()?.join("objects").join(&hash[..2]).join(&hash[2..]))
+}
+
+fn entries_dir() -> CommandResult<PathBuf> {
+    Ok(history_dir()?.join("entries"))
+}
+
+fn index_path(path: &Path) -> CommandResult<PathBuf> {
+    let key = hash_bytes(path.to_string_lossy().as_bytes())?;
+    Ok(entries_dir()?.join(format!("{}.json", key)))
+}
+
+fn load_index(path: &Path) -> CommandResult<HistoryIndex> {
+    let index_file = index_path(path)?;
+    match fs::read_to_string(&index_file) {
+        Ok(json) => serde_json::from_str(&json).map_err(|e| CommandError::other(e.to_string()).with_path(&index_file)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HistoryIndex {
+            path: path.to_string_lossy().to_string(),
+            snapshots: Vec::new(),
+        }),
+        Err(e) => Err(CommandError::from(e).with_path(&index_file)),
+    }
+}
+
+fn save_index(path: &Path, index: &HistoryIndex) -> CommandResult<()> {
+    let index_file = index_path(path)?;
+    if index.snapshots.is_empty() {
+        let _ = fs::remove_file(&index_file);
+        return Ok(());
+    }
+    let json = serde_json::to_vec_pretty(index).map_err(|e| CommandError::other(e.to_string()))?;
+    if let Some(parent) = index_file.parent() {
+        fs::create_dir_all(parent).with_path(parent)?;
+    }
+    atomic_write(&index_file, &json)
+}
+
+fn all_indexes() -> Vec<HistoryIndex> {
+    let dir = match entries_dir().and_then(|d| fs::read_dir(&d).with_path(&d)) {
+        Ok(dir) => dir,
+        Err(_) => return Vec::new(),
+    };
+    dir.flatten()
+        .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+        .filter_map(|json| serde_json::from_str(&json).ok())
+        .collect()
+}
+
+fn count_references() -> HashMap<String, usize> {
+    let mut references = HashMap::new();
+    for index in all_indexes() {
+        for snapshot in index.snapshots {
+            *references.entry(snapshot.hash).or_insert(0) += 1;
+        }
+    }
+    references
+}
+
+fn release(references: &mut HashMap<String, usize>, hash: &str) {
+    let remaining = match references.get_mut(hash) {
+        Some(count) => {
+            *count = count.saturating_sub(1);
+            *count
+        }
+        None => 0,
+    };
+    if remaining == 0 {
+        references.remove(hash);
+        if let Ok(object) = object_path(hash) {
+            let _ = fs::remove_file(object);
+        }
+    }
+}
+
+pub(crate) fn record_snapshot(path: &Path, content: &[u8], source: &str) -> CommandResult<()> {
+    if content.len() > MAX_SNAPSHOT_SIZE {
+        return Ok(());
+    }
+
+    let hash = hash_bytes(content)?;
+    let mut references = REFERENCES.lock().unwrap();
+    let references = references.get_or_insert_with(count_references);
+    let object = object_path(&hash)?;
+    if !object.exists() {
+        if let Some(parent) = object.parent() {
+            fs::create_dir_all(parent).with_path(parent)?;
+        }
+        atomic_write(&object, content)?;
+    }
+
+    let mut index = load_index(path)?;
+    if index.snapshots.last().is_some_and(|s| s.hash == hash) {
+        return Ok(());
+    }
+
+    let timestamp = now_millis();
+    index.snapshots.push(HistorySnapshot {
+        id: format!("{}-{}", timestamp, &hash[..8]),
+        path: path.to_string_lossy().to_string(),
+        timestamp,
+        size: content.len() as u64,
+        hash: hash.clone(),
+        source: source.to_string(),
+    });
+
+    let mut removed = Vec::new();
+    let cutoff = timestamp.saturating_sub(MAX_SNAPSHOT_AGE_MS);
+    let excess = index.snapshots.len().saturating_sub(MAX_SNAPSHOTS_PER_FILE);
+    let mut position = 0;
+    index.snapshots.retain(|snapshot| {
+        position += 1;
+        let keep = position > excess && snapshot.timestamp >= cutoff;
+        if !keep {
+            removed.push(snapshot.hash.clone());
+        }
+        keep
+    });
+
+    save_index(path, &index)?;
+    *references.entry(hash).or_insert(0) += 1;
+    for hash in removed {
+        release(references, &hash);
+    }
+    Ok(())
+}
+
+struct DeletionBudget {
+    files: usize,
+    bytes: u64,
+}
+
+fn record_deleted_files(path: &Path, budget: &mut DeletionBudget) -> CommandResult<()> {
+    if budget.files == 0 {
+        return Ok(());
+    }
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(m) => m,
+        Err(_) => return Ok(()),
+    };
+    if metadata.is_dir() {
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                record_deleted_files(&entry.path(), budget)?;
+            }
+        }
+    } else if metadata.is_file() && metadata.len() as usize <= MAX_SNAPSHOT_SIZE && metadata.len() <= budget.bytes {
+        budget.files -= 1;
+        budget.bytes -= metadata.len();
+        if let Ok(content) = fs::read(path) {
+            record_snapshot(path, &content, "delete")?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn record_deletion(path: &Path) -> CommandResult<()> {
+    let mut budget = DeletionBudget {
+        files: MAX_DELETED_DIR_FILES,
+        bytes: MAX_DELETED_DIR_BYTES,
+    };
+    record_deleted_files(path, &mut budget)
+}
+
+fn prune_expired() -> CommandResult<Vec<HistoryIndex>> {
+    let cutoff = now_millis().saturating_sub(MAX_SNAPSHOT_AGE_MS);
+    let mut references = REFERENCES.lock().unwrap();
+    let references = references.get_or_insert_with(count_references);
+    let mut indexes = all_indexes();
+    for index in &mut indexes {
+        if index.snapshots.iter().all(|s| s.timestamp >= cutoff) {
+            continue;
+        }
+        let (kept, expired): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut index.snapshots).into_iter().partition(|s| s.timestamp >= cutoff);
+        index.snapshots = kept;
+        save_index(Path::new(&index.path), index)?;
+        for snapshot in expired {
+            release(references, &snapshot.hash);
+        }
+    }
+    indexes.retain(|index| !index.snapshots.is_empty());
+    Ok(indexes)
+}
+
+fn find_snapshot(path: &Path, snapshot_id: &str) -> CommandResult<(HistorySnapshot, Vec<u8>)> {
+    let index = load_index(path)?;
+    let snapshot = index
+        .snapshots
+        .into_iter()
+        .find(|s| s.id == snapshot_id)
+        .ok_or_else(|| CommandError::not_found("Snapshot not found").with_path(path))?;
+    let object = object_path(&snapshot.hash)?;
+    let content = fs::read(&object).with_path(&object)?;
+    Ok((snapshot, content))
+}
+
+#[tauri::command]
+pub fn history_list(path: &str) -> CommandResult<Vec<HistorySnapshot>> {
+    let resolved = ensure_allowed(path)?;
+    let mut snapshots = load_index(&resolved)?.snapshots;
+    snapshots.reverse();
+    Ok(snapshots)
+}
+
+#[tauri::command]
+pub fn history_diff(path: &str, snapshot_id: &str) -> CommandResult<GitDiff> {
+    let resolved = ensure_allowed(path)?;
+    let (_, old_content) = find_snapshot(&resolved, snapshot_id)?;
+    let new_content = match fs::read(&resolved) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(CommandError::from(e).with_path(path)),
+    };
+
+    Ok(GitDiff {
+        path: path.to_string(),
+        hunks: diff_buffers(&old_content, &new_content, path, None)?,
+        lfs: None,
+    })
+}
+
+#[tauri::command]
+pub fn history_restore(path: &str, snapshot_id: &str) -> CommandResult<FileVersion> {
+    let resolved = ensure_allowed(path)?;
+    let (_, content) = find_snapshot(&resolved, snapshot_id)?;
+
+    match fs::read(&resolved) {
+        Ok(current) => record_snapshot(&resolved, &current, "restore")?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            if let Some(parent) = resolved.parent() {
+                fs::create_dir_all(parent).with_path(parent)?;
+            }
+        }
+        Err(e) => return Err(CommandError::from(e).with_path(path)),
+    }
+
+    atomic_write(&resolved, &content)?;
+    let metadata = fs::metadata(&resolved).with_path(path)?;
+    Ok(FileVersion::from_bytes(&metadata, &content))
+}
+
+#[tauri::command]
+pub fn history_deleted(root: &str) -> CommandResult<Vec<HistorySnapshot>> {
+    let root = ensure_allowed(root)?;
+    let mut deleted: Vec<HistorySnapshot> = prune_expired()?
+        .into_iter()
+        .filter(|index| {
+            let path = Path::new(&index.path);
+            path.starts_with(&root) && fs::symlink_metadata(path).is_err()
+        })
+        .filter_map(|index| index.snapshots.into_iter().last())
+        .collect();
+    deleted.sort_by_key(|s| std::cmp::Reverse(s.timestamp));
+    Ok(deleted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::workspace::trust_root;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("litcode-history-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        trust_root(&dir).unwrap()
+    }
+
+    fn list(path: &Path) -> Vec<HistorySnapshot> {
+        history_list(path.to_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn snapshots_skip_unchanged_content() {
+        let root = scratch_dir("dedup");
+        let path = root.join("file.txt");
+        record_snapshot(&path, b"dedup one", "save").unwrap();
+        record_snapshot(&path, b"dedup one", "save").unwrap();
+        record_snapshot(&path, b"dedup two", "save").unwrap();
+
+        let snapshots = list(&path);
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].size, 9);
+        assert_eq!(find_snapshot(&path, &snapshots[1].id).unwrap().1, b"dedup one");
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn deletion_is_recorded_before_the_files_go_away() {
+        let root = scratch_dir("deletion");
+        fs::create_dir_all(root.join("dir/nested")).unwrap();
+        fs::write(root.join("dir/a.txt"), "deleted a").unwrap();
+        fs::write(root.join("dir/nested/b.txt"), "deleted b").unwrap();
+
+        record_deletion(&root.join("dir")).unwrap();
+        fs::remove_dir_all(root.join("dir")).unwrap();
+
+        let deleted = history_deleted(root.to_str().unwrap()).unwrap();
+        assert_eq!(deleted.len(), 2);
+        let b = deleted.iter().find(|s| s.path.ends_with("b.txt")).unwrap();
+        history_restore(&b.path, &b.id).unwrap();
+        assert_eq!(fs::read_to_string(root.join("dir/nested/b.txt")).unwrap(), "deleted b");
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn expired_snapshots_are_pruned() {
+        let root = scratch_dir("expired");
+        let path = root.join("old.txt");
+        record_snapshot(&path, b"expired content", "save").unwrap();
+        let mut index = load_index(&path).unwrap();
+        index.snapshots[0].timestamp = now_millis() - MAX_SNAPSHOT_AGE_MS - 1;
+        save_index(&path, &index).unwrap();
+        let object = object_path(&index.snapshots[0].hash).unwrap();
+        assert!(object.exists());
+
+        assert!(history_deleted(root.to_str().unwrap()).unwrap().is_empty());
+        assert!(list(&path).is_empty());
+        assert!(!object.exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,398 @@
+use super::error::{CommandError, CommandResult, ResultExt};
+use super::fs::{create_new_file, mtime_millis, remove_path_permanently, rename_entry, FileVersion};
+use super::trash::{trash_entry, trash_restore};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MAX_JOURNAL_ENTRIES: usize = 100;
+const MAX_HASHED_SIZE: u64 = 16 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FsOperationKind {
+    Rename,
+    CreateFile,
+    CreateDir,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Fingerprint {
+    Directory,
+    Content(u64, String),
+    Modified(u64, u64),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FsOperation {
+    pub id: u64,
+    pub kind: FsOperationKind,
+    pub path: String,
+    pub target: Option<String>,
+    #[serde(rename = "trashId")]
+    pub trash_id: Option<String>,
+    pub timestamp: u64,
+    #[serde(skip)]
+    fingerprint: Option<Fingerprint>,
+    #[serde(skip)]
+    content: Vec<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FsJournal {
+    pub undo: Vec<FsOperation>,
+    pub redo: Vec<FsOperation>,
+}
+
+type Stacks = (Vec<FsOperation>, Vec<FsOperation>);
+
+static JOURNAL: Mutex<Stacks> = Mutex::new((Vec::new(), Vec::new()));
+static STEPPING: Mutex<()> = Mutex::new(());
+
+pub(crate) fn fingerprint(path: &Path) -> Option<Fingerprint> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    if metadata.is_dir() {
+        return Some(Fingerprint::Directory);
+    }
+    if metadata.is_file() && metadata.len() <= MAX_HASHED_SIZE {
+        if let Ok(bytes) = fs::read(path) {
+            let hash = FileVersion::from_bytes(&metadata, &bytes).hash;
+            return Some(Fingerprint::Content(metadata.len(), hash));
+        }
+    }
+    Some(Fingerprint::Modified(metadata.len(), mtime_millis(&metadata)))
+}
+
+impl FsOperation {
+    fn new(kind: FsOperationKind, path: &Path, target: Option<&Path>) -> Self {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        FsOperation {
+            id: COUNTER.fetch_add(1, Ordering::Relaxed),
+            kind,
+            path: path.to_string_lossy().to_string(),
+            target: target.map(|t| t.to_string_lossy().to_string()),
+            trash_id: None,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            fingerprint: None,
+            content: Vec::new(),
+        }
+    }
+
+    fn restorable(&self) -> bool {
+        self.kind != FsOperationKind::Delete || self.trash_id.as_deref().is_some_and(|id| !id.is_empty())
+    }
+
+    fn target(&self) -> CommandResult<&Path> {
+        self.target
+            .as_deref()
+            .map(Path::new)
+            .ok_or_else(|| CommandError::other("Journal entry has no target"))
+    }
+
+    fn undo(&mut self) -> CommandResult<()> {
+        let path = Path::new(&self.path);
+        match self.kind {
+            FsOperationKind::Rename => {
+                let target = self.target()?;
+                verify(target, self.fingerprint.as_ref())?;
+                ensure_missing(path)?;
+                rename_entry(target, path)?;
+                self.fingerprint = fingerprint(path);
+            }
+            FsOperationKind::CreateFile => {
+                verify(path, self.fingerprint.as_ref())?;
+                self.content = fs::read(path).with_path(path)?;
+                remove_path_permanently(path).with_path(path)?;
+            }
+            FsOperationKind::CreateDir => {
+                if !only_empty_dirs(path) {
+                    return Err(CommandError::conflict("Folder is no longer empty").with_path(path));
+                }
+                fs::remove_dir_all(path).with_path(path)?;
+            }
+            FsOperationKind::Delete => {
+                ensure_missing(path)?;
+                trash_restore(self.trash_id.as_deref().unwrap_or_default()).with_path(path)?;
+                self.fingerprint = fingerprint(path);
+            }
+        }
+        Ok(())
+    }
+
+    fn redo(&mut self) -> CommandResult<()> {
+        let path = Path::new(&self.path);
+        match self.kind {
+            FsOperationKind::Rename => {
+                let target = self.target()?;
+                verify(path, self.fingerprint.as_ref())?;
+                ensure_missing(target)?;
+                rename_entry(path, target)?;
+                self.fingerprint = fingerprint(target);
+            }
+            FsOperationKind::CreateFile => {
+                ensure_missing(path)?;
+                create_new_file(path, &self.content)?;
+                self.fingerprint = fingerprint(path);
+            }
+            FsOperationKind::CreateDir => {
+                ensure_missing(path)?;
+                let target = self.target()?;
+                fs::create_dir_all(target).with_path(target)?;
+            }
+            FsOperationKind::Delete => {
+                verify(path, self.fingerprint.as_ref())?;
+                self.trash_id = Some(trash_entry(path)?.id);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn verify(path: &Path, expected: Option<&Fingerprint>) -> CommandResult<()> {
+    match fingerprint(path) {
+        Some(current) if expected.is_none_or(|e| *e == current) => Ok(()),
+        Some(_) => Err(CommandError::conflict("Entry has changed on disk since the operation").with_path(path)),
+        None => Err(CommandError::conflict("Entry no longer exists").with_path(path)),
+    }
+}
+
+fn ensure_missing(path: &Path) -> CommandResult<()> {
+    if fs::symlink_metadata(path).is_ok() {
+        return Err(CommandError::conflict("Another entry now exists at this path").with_path(path));
+    }
+    Ok(())
+}
+
+fn only_empty_dirs(path: &Path) -> bool {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::read_dir(path)
+            .map(|entries| entries.flatten().all(|entry| only_empty_dirs(&entry.path())))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+fn push(operation: FsOperation) {
+    push_onto(&JOURNAL, operation);
+}
+
+fn push_onto(journal: &Mutex<Stacks>, operation: FsOperation) {
+    let mut journal = journal.lock().unwrap();
+    journal.1.clear();
+    journal.0.push(operation);
+    if journal.0.len() > MAX_JOURNAL_ENTRIES {
+        journal.0.remove(0);
+    }
+}
+
+pub(crate) fn record_rename(from: &Path, to: &Path) {
+    let mut operation = FsOperation::new(FsOperationKind::Rename, from, Some(to));
+    operation.fingerprint = fingerprint(to);
+    push(operation);
+}
+
+pub(crate) fn record_create_file(path: &Path, content: &[u8]) {
+    let mut operation = FsOperation::new(FsOperationKind::CreateFile, path, None);
+    operation.fingerprint = fingerprint(path);
+    operation.content = content.to_vec();
+    push(operation);
+}
+
+pub(crate) fn record_create_dir(top: &Path, leaf: &Path) {
+    push(FsOperation::new(FsOperationKind::CreateDir, top, Some(leaf)));
+}
+
+pub(crate) fn record_delete(path: &Path, trash_id: &str, before: Option<Fingerprint>) {
+    if trash_id.is_empty() {
+        return;
+    }
+    let mut operation = FsOperation::new(FsOperationKind::Delete, path, None);
+    operation.trash_id = Some(trash_id.to_string());
+    operation.fingerprint = before;
+    push(operation);
+}
+
+fn step(journal: &Mutex<Stacks>, undo: bool) -> CommandResult<Option<FsOperation>> {
+    let _stepping = STEPPING.lock().unwrap();
+    let popped = {
+        let stacks = &mut *journal.lock().unwrap();
+        if undo {
+            stacks.0.pop()
+        } else {
+            stacks.1.pop()
+        }
+    };
+    let mut operation = match popped {
+        Some(operation) => operation,
+        None => return Ok(None),
+    };
+
+    let result = if undo { operation.undo() } else { operation.redo() };
+    let mut stacks = journal.lock().unwrap();
+    let (undo_stack, redo_stack) = &mut *stacks;
+    let (from, to) = if undo {
+        (undo_stack, redo_stack)
+    } else {
+        (redo_stack, undo_stack)
+    };
+    if let Err(e) = result {
+        from.push(operation);
+        return Err(e);
+    }
+    if operation.restorable() {
+        to.push(operation.clone());
+    }
+    Ok(Some(operation))
+}
+
+#[tauri::command]
+pub fn fs_undo() -> CommandResult<Option<FsOperation>> {
+    step(&JOURNAL, true)
+}
+
+#[tauri::command]
+pub fn fs_redo() -> CommandResult<Option<FsOperation>> {
+    step(&JOURNAL, false)
+}
+
+#[tauri::command]
+pub fn fs_journal() -> FsJournal {
+    let journal = JOURNAL.lock().unwrap();
+    FsJournal {
+        undo: journal.0.iter().rev().cloned().collect(),
+        redo: journal.1.iter().rev().cloned().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::workspace::trust_root;
+    use std::path::PathBuf;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("litcode-journal-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        trust_root(&dir).unwrap()
+    }
+
+    fn journal_with(operation: FsOperation) -> Mutex<Stacks> {
+        let journal = Mutex::new((Vec::new(), Vec::new()));
+        push_onto(&journal, operation);
+        journal
+    }
+
+    fn depths(journal: &Mutex<Stacks>) -> (usize, usize) {
+        let stacks = journal.lock().unwrap();
+        (stacks.0.len(), stacks.1.len())
+    }
+
+    #[test]
+    fn rename_undo_and_redo() {
+        let root = scratch_dir("rename");
+        fs::write(root.join("b.txt"), "data").unwrap();
+        let (from, to) = (root.join("a.txt"), root.join("b.txt"));
+        let mut operation = FsOperation::new(FsOperationKind::Rename, &from, Some(&to));
+        operation.fingerprint = fingerprint(&root.join("b.txt"));
+        let journal = journal_with(operation);
+
+        assert!(step(&journal, true).unwrap().is_some());
+        assert!(root.join("a.txt").exists() && !root.join("b.txt").exists());
+        assert_eq!(depths(&journal), (0, 1));
+
+        assert!(step(&journal, false).unwrap().is_some());
+        assert!(!root.join("a.txt").exists() && root.join("b.txt").exists());
+        assert_eq!(depths(&journal), (1, 0));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn undo_conflict_keeps_entry() {
+        let root = scratch_dir("conflict");
+        fs::write(root.join("b.txt"), "data").unwrap();
+        let (from, to) = (root.join("a.txt"), root.join("b.txt"));
+        let mut operation = FsOperation::new(FsOperationKind::Rename, &from, Some(&to));
+        operation.fingerprint = fingerprint(&root.join("b.txt"));
+        let journal = journal_with(operation);
+        fs::write(root.join("b.txt"), "changed").unwrap();
+
+        assert!(step(&journal, true).is_err());
+        assert_eq!(depths(&journal), (1, 0));
+        assert_eq!(fs::read_to_string(root.join("b.txt")).unwrap(), "changed");
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn create_file_undo_keeps_content_for_redo() {
+        let root = scratch_dir("create");
+        let path = root.join("new.txt");
+        fs::write(&path, "hello").unwrap();
+        let mut operation = FsOperation::new(FsOperationKind::CreateFile, &path, None);
+        operation.fingerprint = fingerprint(&path);
+        let journal = journal_with(operation);
+        fs::write(&path, "edited").unwrap();
+
+        assert!(step(&journal, true).is_err());
+        fs::write(&path, "hello").unwrap();
+        step(&journal, true).unwrap();
+        assert!(!path.exists());
+        step(&journal, false).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "hello");
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn create_dir_undo_requires_empty_folder() {
+        let root = scratch_dir("create-dir");
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        fs::write(root.join("a/b/file.txt"), "x").unwrap();
+        let (top, leaf) = (root.join("a"), root.join("a/b"));
+        let journal = journal_with(FsOperation::new(FsOperationKind::CreateDir, &top, Some(&leaf)));
+
+        assert!(step(&journal, true).is_err());
+        fs::remove_file(root.join("a/b/file.txt")).unwrap();
+        step(&journal, true).unwrap();
+        assert!(!root.join("a").exists());
+        step(&journal, false).unwrap();
+        assert!(root.join("a/b").is_dir());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn delete_undo_restores_from_trash() {
+        let root = scratch_dir("delete");
+        let path = root.join("file.txt");
+        fs::write(&path, "data").unwrap();
+        let before = fingerprint(&path);
+        let item = crate::commands::trash::move_to_litcode_trash(&path).unwrap();
+        let mut operation = FsOperation::new(FsOperationKind::Delete, &path, None);
+        operation.trash_id = Some(item.id);
+        operation.fingerprint = before;
+        let journal = journal_with(operation);
+
+        step(&journal, true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "data");
+        assert_eq!(depths(&journal), (0, 1));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn unrestorable_delete_is_not_journaled() {
+        let root = scratch_dir("unrestorable");
+        let path = root.join("gone.txt");
+        record_delete(&path, "", None);
+        let path = path.to_string_lossy().to_string();
+        assert!(!fs_journal().undo.iter().any(|operation| operation.path == path));
+        assert!(!FsOperation::new(FsOperationKind::Delete, Path::new(&path), None).restorable());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
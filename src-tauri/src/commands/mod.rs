@@ -1,11 +1,48 @@
+pub mod archive;
+pub mod backup;
+pub mod checkpoint;
+pub mod compare;
 pub mod config;
+pub mod disk_usage;
+pub mod editorconfig;
+pub mod encoding;
+pub mod error;
+pub mod file_view;
 pub mod fs;
 pub mod git;
+pub mod history;
+pub mod journal;
+pub mod permissions;
+pub mod templates;
 pub mod terminal;
+pub mod rename;
 pub mod search;
+pub mod transfer;
+pub mod trash;
+pub mod tree;
+pub mod util;
+pub mod watch;
+pub mod workspace;
 
+pub use archive::*;
+pub use backup::*;
+pub use checkpoint::*;
+pub use compare::*;
 pub use config::*;
+pub use disk_usage::*;
+pub use editorconfig::*;
+pub use file_view::*;
 pub use fs::*;
 pub use git::*;
+pub use history::*;
+pub use journal::*;
+pub use permissions::*;
+pub use templates::*;
 pub use terminal::*;
+pub use rename::*;
 pub use search::*;
+pub use transfer::*;
+pub use trash::*;
+pub use tree::*;
+pub use watch::*;
+pub use workspace::*;
@@ -0,0 +1,322 @@
+use super::error::{CommandError, CommandResult, ResultExt};
+use super::fs::{is_executable, ownership, permission_bits};
+use super::workspace::ensure_allowed;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FilePermissions {
+    pub path: String,
+    pub mode: Option<u32>,
+    #[serde(rename = "modeString")]
+    pub mode_string: Option<String>,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub readonly: bool,
+    #[serde(rename = "isExecutable")]
+    pub is_executable: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PermissionsResult {
+    pub permissions: FilePermissions,
+    pub changed: usize,
+}
+
+enum ModeSpec {
+    Absolute(u32),
+    Symbolic(Vec<Clause>),
+}
+
+struct Clause {
+    who: u32,
+    actions: Vec<(char, String)>,
+}
+
+fn parse_mode(spec: &str) -> CommandResult<ModeSpec> {
+    let spec = spec.trim();
+    let invalid = || CommandError::invalid_input(format!("Invalid mode '{}'", spec));
+    if spec.is_empty() {
+        return Err(invalid());
+    }
+    if spec.chars().all(|c| c.is_ascii_digit()) {
+        if spec.len() > 4 {
+            return Err(invalid());
+        }
+        return u32::from_str_radix(spec, 8).map(ModeSpec::Absolute).map_err(|_| invalid());
+    }
+
+    let mut clauses = Vec::new();
+    for part in spec.split(',') {
+        let op_start = part.find(['+', '-', '=']).ok_or_else(invalid)?;
+        let mut who = 0;
+        for c in part[..op_start].chars() {
+            who |= match c {
+                'u' => 0o4700,
+                'g' => 0o2070,
+                'o' => 0o1007,
+                'a' => 0o7777,
+                _ => return Err(invalid()),
+            };
+        }
+        if who == 0 {
+            who = 0o7777;
+        }
+
+        let mut actions: Vec<(char, String)> = Vec::new();
+        for c in part[op_start..].chars() {
+            match c {
+                '+' | '-' | '=' => actions.push((c, String::new())),
+                'r' | 'w' | 'x' | 'X' | 's' | 't' => actions.last_mut().ok_or_else(invalid)?.1.push(c),
+                _ => return Err(invalid()),
+            }
+        }
+        clauses.push(Clause { who, actions });
+    }
+    Ok(ModeSpec::Symbolic(clauses))
+}
+
+fn apply_mode(spec: &ModeSpec, current: u32, is_dir: bool) -> u32 {
+    let clauses = match spec {
+        ModeSpec::Absolute(mode) => return *mode,
+        ModeSpec::Symbolic(clauses) => clauses,
+    };
+
+    let mut mode = current & 0o7777;
+    for clause in clauses {
+        for (op, perms) in &clause.actions {
+            let mut bits = 0;
+            for perm in perms.chars() {
+                bits |= match perm {
+                    'r' => 0o444,
+                    'w' => 0o222,
+                    'x' => 0o111,
+                    'X' if is_dir || current & 0o111 != 0 => 0o111,
+                    's' => 0o6000,
+                    't' => 0o1000,
+                    _ => 0,
+                };
+            }
+            bits &= clause.who;
+            match op {
+                '+' => mode |= bits,
+                '-' => mode &= !bits,
+                _ => mode = (mode & !clause.who) | bits,
+            }
+        }
+    }
+    mode
+}
+
+fn mode_string(mode: u32, is_dir: bool) -> String {
+    let mut out = String::with_capacity(10);
+    out.push(if is_dir { 'd' } else { '-' });
+    let special = [(0o4000, 's', 'S'), (0o2000, 's', 'S'), (0o1000, 't', 'T')];
+    for (i, (flag, set, unset)) in special.iter().enumerate() {
+        let shift = 6 - i * 3;
+        let triple = (mode >> shift) & 0o7;
+        out.push(if triple & 0o4 != 0 { 'r' } else { '-' });
+        out.push(if triple & 0o2 != 0 { 'w' } else { '-' });
+        out.push(match (triple & 0o1 != 0, mode & flag != 0) {
+            (true, true) => *set,
+            (false, true) => *unset,
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+    }
+    out
+}
+
+fn read_permissions(path: &Path) -> CommandResult<FilePermissions> {
+    let metadata = fs::metadata(path).with_path(path)?;
+    let mode = permission_bits(&metadata);
+    let owner = ownership(&metadata);
+    Ok(FilePermissions {
+        path: path.to_string_lossy().to_string(),
+        mode,
+        mode_string: mode.map(|m| mode_string(m, metadata.is_dir())),
+        uid: owner.map(|o| o.0),
+        gid: owner.map(|o| o.1),
+        readonly: metadata.permissions().readonly(),
+        is_executable: is_executable(path, &metadata),
+    })
+}
+
+#[cfg(unix)]
+fn change_entry(
+    path: &Path,
+    metadata: &fs::Metadata,
+    mode: Option<&ModeSpec>,
+    owner: (Option<u32>, Option<u32>),
+) -> CommandResult<bool> {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    let mut changed = false;
+    if owner.0.is_some_and(|uid| uid != metadata.uid()) || owner.1.is_some_and(|gid| gid != metadata.gid()) {
+        std::os::unix::fs::chown(path, owner.0, owner.1).with_path(path)?;
+        changed = true;
+    }
+    if let Some(spec) = mode {
+        let current = metadata.permissions().mode() & 0o7777;
+        let updated = apply_mode(spec, current, metadata.is_dir());
+        if updated != current {
+            fs::set_permissions(path, fs::Permissions::from_mode(updated)).with_path(path)?;
+            changed = true;
+        }
+    }
+    Ok(changed)
+}
+
+#[cfg(unix)]
+fn change_tree(
+    path: &Path,
+    mode: Option<&ModeSpec>,
+    owner: (Option<u32>, Option<u32>),
+    recursive: bool,
+) -> CommandResult<usize> {
+    let metadata = fs::metadata(path).with_path(path)?;
+    if !recursive || !metadata.is_dir() {
+        return Ok(change_entry(path, &metadata, mode, owner)? as usize);
+    }
+
+    let mut changed = 0;
+    let mut changed_first = false;
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            changed += change_entry(path, &metadata, mode, owner)? as usize;
+            changed_first = true;
+            fs::read_dir(path).with_path(path)?
+        }
+        Err(e) => return Err(CommandError::from(e).with_path(path)),
+    };
+    let children: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+    for child in children {
+        changed += change_child(&child, mode, owner)?;
+    }
+    if !changed_first {
+        changed += change_entry(path, &metadata, mode, owner)? as usize;
+    }
+    Ok(changed)
+}
+
+#[cfg(unix)]
+fn change_child(path: &Path, mode: Option<&ModeSpec>, owner: (Option<u32>, Option<u32>)) -> CommandResult<usize> {
+    let metadata = fs::symlink_metadata(path).with_path(path)?;
+    if metadata.file_type().is_symlink() {
+        return Ok(0);
+    }
+    change_tree(path, mode, owner, true)
+}
+
+#[cfg(not(unix))]
+fn change_tree(
+    path: &Path,
+    _mode: Option<&ModeSpec>,
+    _owner: (Option<u32>, Option<u32>),
+    _recursive: bool,
+) -> CommandResult<usize> {
+    Err(CommandError::new(
+        super::error::ErrorKind::Unsupported,
+        "POSIX permissions are not supported on this platform",
+    )
+    .with_path(path))
+}
+
+#[tauri::command]
+pub fn get_file_permissions(path: &str) -> CommandResult<FilePermissions> {
+    let resolved = ensure_allowed(path)?;
+    read_permissions(&resolved)
+}
+
+#[tauri::command]
+pub fn set_file_permissions(
+    path: &str,
+    mode: Option<String>,
+    recursive: Option<bool>,
+    uid: Option<u32>,
+    gid: Option<u32>,
+) -> CommandResult<PermissionsResult> {
+    let resolved = ensure_allowed(path)?;
+    let spec = mode.as_deref().map(parse_mode).transpose()?;
+    if spec.is_none() && uid.is_none() && gid.is_none() {
+        return Err(CommandError::invalid_input("Nothing to change"));
+    }
+    let changed = change_tree(&resolved, spec.as_ref(), (uid, gid), recursive.unwrap_or(false))?;
+    Ok(PermissionsResult {
+        permissions: read_permissions(&resolved)?,
+        changed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(spec: &str, current: u32, is_dir: bool) -> u32 {
+        apply_mode(&parse_mode(spec).unwrap(), current, is_dir)
+    }
+
+    #[test]
+    fn parse_mode_rejects_malformed_specs() {
+        for spec in ["", "  ", "99", "12345", "u", "z+x", "u+q", "rw", "u+x,"] {
+            assert!(parse_mode(spec).is_err(), "{:?} should be rejected", spec);
+        }
+        assert!(matches!(parse_mode("0755").unwrap(), ModeSpec::Absolute(0o755)));
+        assert!(matches!(parse_mode(" 644 ").unwrap(), ModeSpec::Absolute(0o644)));
+    }
+
+    #[test]
+    fn apply_mode_handles_symbolic_clauses() {
+        assert_eq!(apply("755", 0o600, false), 0o755);
+        assert_eq!(apply("u+x", 0o644, false), 0o744);
+        assert_eq!(apply("+x", 0o644, false), 0o755);
+        assert_eq!(apply("go-rwx", 0o755, false), 0o700);
+        assert_eq!(apply("u=rw,g=r,o=", 0o777, false), 0o640);
+        assert_eq!(apply("a-w+x", 0o666, false), 0o555);
+        assert_eq!(apply("u+s,o+t", 0o755, true), 0o5755);
+        assert_eq!(apply("g=", 0o2775, true), 0o705);
+    }
+
+    #[test]
+    fn apply_mode_capital_x_only_for_dirs_and_executables() {
+        assert_eq!(apply("a+X", 0o644, false), 0o644);
+        assert_eq!(apply("a+X", 0o744, false), 0o755);
+        assert_eq!(apply("a+X", 0o644, true), 0o755);
+    }
+
+    #[test]
+    fn mode_string_shows_special_bits() {
+        assert_eq!(mode_string(0o755, true), "drwxr-xr-x");
+        assert_eq!(mode_string(0o4644, false), "-rwSr--r--");
+        assert_eq!(mode_string(0o1777, true), "drwxrwxrwt");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn recursive_change_skips_symlinks() {
+        use crate::commands::workspace::trust_root;
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("litcode-permissions-tree-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        let dir = trust_root(&dir).unwrap();
+        let outside = std::env::temp_dir().join(format!("litcode-permissions-outside-{}", std::process::id()));
+        fs::write(&outside, "").unwrap();
+        fs::set_permissions(&outside, fs::Permissions::from_mode(0o644)).unwrap();
+        fs::write(dir.join("sub/a.txt"), "").unwrap();
+        fs::set_permissions(dir.join("sub/a.txt"), fs::Permissions::from_mode(0o644)).unwrap();
+        std::os::unix::fs::symlink(&outside, dir.join("sub/link")).unwrap();
+
+        let result = set_file_permissions(dir.to_str().unwrap(), Some("go-r".into()), Some(true), None, None).unwrap();
+
+        assert!(result.changed >= 2);
+        let mode = |p: &Path| fs::metadata(p).unwrap().permissions().mode() & 0o7777;
+        assert_eq!(mode(&dir.join("sub/a.txt")), 0o600);
+        assert_eq!(mode(&outside), 0o644);
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_file(&outside).unwrap();
+    }
+}
@@ -0,0 +1,542 @@
+use super::error::{CommandError, CommandResult, ErrorKind, ResultExt};
+use super::fs::{atomic_write, rename_entry, FileVersion};
+use super::search::search_content;
+use super::util::percent_decode;
+use super::workspace::{ensure_allowed, ensure_entry_allowed};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReferenceEdit {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+    #[serde(rename = "oldText")]
+    pub old_text: String,
+    #[serde(rename = "newText")]
+    pub new_text: String,
+    #[serde(rename = "lineContent")]
+    pub line_content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FileReferenceEdits {
+    pub path: String,
+    pub version: FileVersion,
+    pub edits: Vec<ReferenceEdit>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RenamePreview {
+    #[serde(rename = "oldPath")]
+    pub old_path: String,
+    #[serde(rename = "newPath")]
+    pub new_path: String,
+    pub source: String,
+    pub files: Vec<FileReferenceEdits>,
+    pub truncated: bool,
+}
+
+type RawEdits = BTreeMap<PathBuf, Vec<(usize, usize, String)>>;
+
+struct LoadedText {
+    content: String,
+    version: FileVersion,
+}
+
+fn load_text(path: &Path) -> CommandResult<LoadedText> {
+    let bytes = fs::read(path).with_path(path)?;
+    let metadata = fs::metadata(path).with_path(path)?;
+    let version = FileVersion::from_bytes(&metadata, &bytes);
+    let content =
+        String::from_utf8(bytes).map_err(|_| CommandError::invalid_input("File is not valid UTF-8").with_path(path))?;
+    Ok(LoadedText { content, version })
+}
+
+fn line_starts(content: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect()
+}
+
+fn make_edit(content: &str, starts: &[usize], start: usize, end: usize, new_text: String) -> ReferenceEdit {
+    let line = starts.partition_point(|s| *s <= start) - 1;
+    let line_start = starts[line];
+    let line_end = content[line_start..]
+        .find('\n')
+        .map(|i| line_start + i)
+        .unwrap_or(content.len());
+    ReferenceEdit {
+        start,
+        end,
+        line: line + 1,
+        column: start - line_start + 1,
+        old_text: content[start..end].to_string(),
+        new_text,
+        line_content: content[line_start..line_end].trim_end_matches('\r').to_string(),
+    }
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            c => normalized.push(c),
+        }
+    }
+    normalized
+}
+
+fn relative_spec(from_dir: &Path, target: &Path) -> String {
+    let from: Vec<_> = from_dir.components().collect();
+    let to: Vec<_> = target.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    parts.extend(to[common..].iter().map(|c| c.as_os_str().to_string_lossy().to_string()));
+    let spec = parts.join("/");
+    if spec.starts_with("..") {
+        spec
+    } else {
+        format!("./{}", spec)
+    }
+}
+
+struct RenameMapping {
+    old: PathBuf,
+    new: PathBuf,
+    is_dir: bool,
+}
+
+impl RenameMapping {
+    fn without_extension(path: &Path) -> PathBuf {
+        path.with_extension("")
+    }
+
+    fn map(&self, path: &Path) -> Option<PathBuf> {
+        if let Ok(rest) = path.strip_prefix(&self.old) {
+            return Some(if rest.as_os_str().is_empty() { self.new.clone() } else { self.new.join(rest) });
+        }
+        if !self.is_dir && path == Self::without_extension(&self.old) {
+            return Some(Self::without_extension(&self.new));
+        }
+        None
+    }
+
+    fn moved_location(&self, file: &Path) -> PathBuf {
+        self.map(file).unwrap_or_else(|| file.to_path_buf())
+    }
+}
+
+fn quoted_spec(line: &str, match_start: usize, match_end: usize) -> Option<(usize, usize)> {
+    let before = &line[..match_start];
+    let open = before.rfind(['"', '\'', '`'])?;
+    let quote = line.as_bytes()[open] as char;
+    let close = match_end + line[match_end..].find(quote)?;
+    Some((open + 1, close))
+}
+
+fn rewrite_spec(spec: &str, file_dir: &Path, new_file_dir: &Path, mapping: &RenameMapping) -> Option<String> {
+    if !spec.starts_with("./") && !spec.starts_with("../") {
+        return None;
+    }
+    let target = normalize(&file_dir.join(spec));
+    let new_target = match mapping.map(&target) {
+        Some(mapped) => mapped,
+        None if file_dir == new_file_dir => return None,
+        None => target,
+    };
+    let new_spec = relative_spec(new_file_dir, &new_target);
+    (new_spec != spec).then_some(new_spec)
+}
+
+fn text_search_edits(root: &str, mapping: &RenameMapping) -> CommandResult<(RawEdits, bool)> {
+    let mut edits = RawEdits::new();
+    let stem = mapping
+        .old
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let result = search_content(root, &stem, Some(true), Some(false))?;
+    let mut matches_by_file: BTreeMap<PathBuf, Vec<(usize, usize, usize)>> = BTreeMap::new();
+    for m in &result.matches {
+        matches_by_file
+            .entry(PathBuf::from(&m.path))
+            .or_default()
+            .push((m.line, m.match_start, m.match_end));
+    }
+
+    for (file, matches) in matches_by_file {
+        if mapping.map(&file).is_some() {
+            continue;
+        }
+        let text = match load_text(&file) {
+            Ok(text) => text,
+            Err(_) => continue,
+        };
+        let starts = line_starts(&text.content);
+        let dir = file.parent().unwrap_or(Path::new("/"));
+        for (line, match_start, match_end) in matches {
+            let line_start = starts[line - 1];
+            let line_text = text.content[line_start..].lines().next().unwrap_or("");
+            let (spec_start, spec_end) = match quoted_spec(line_text, match_start, match_end) {
+                Some(range) => range,
+                None => continue,
+            };
+            let spec = &line_text[spec_start..spec_end];
+            if let Some(new_spec) = rewrite_spec(spec, dir, dir, mapping) {
+                let entry = edits.entry(file.clone()).or_default();
+                let range = (line_start + spec_start, line_start + spec_end, new_spec);
+                if !entry.contains(&range) {
+                    entry.push(range);
+                }
+            }
+        }
+    }
+
+    let spec_pattern = Regex::new(r#"["'`](\.\.?/[^"'`\n]*)["'`]"#).unwrap();
+    let mut moved_files = Vec::new();
+    collect_moved_files(&mapping.old, &mut moved_files);
+    for file in moved_files {
+        let text = match load_text(&file) {
+            Ok(text) => text,
+            Err(_) => continue,
+        };
+        let old_dir = file.parent().unwrap_or(Path::new("/"));
+        let moved = mapping.moved_location(&file);
+        let new_dir = moved.parent().unwrap_or(Path::new("/"));
+        for captures in spec_pattern.captures_iter(&text.content) {
+            let spec = captures.get(1).unwrap();
+            if let Some(new_spec) = rewrite_spec(spec.as_str(), old_dir, new_dir, mapping) {
+                edits.entry(file.clone()).or_default().push((spec.start(), spec.end(), new_spec));
+            }
+        }
+    }
+
+    Ok((edits, result.truncated))
+}
+
+fn collect_moved_files(path: &Path, files: &mut Vec<PathBuf>) {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => {
+            if let Ok(entries) = fs::read_dir(path) {
+                for entry in entries.flatten() {
+                    collect_moved_files(&entry.path(), files);
+                }
+            }
+        }
+        Ok(metadata) if metadata.is_file() && metadata.len() <= 1024 * 1024 => files.push(path.to_path_buf()),
+        _ => {}
+    }
+}
+
+fn uri_to_path(uri: &str) -> CommandResult<PathBuf> {
+    let encoded = uri
+        .strip_prefix("file://")
+        .ok_or_else(|| CommandError::invalid_input(format!("Unsupported document URI: {}", uri)))?;
+    let path = String::from_utf8(percent_decode(encoded))
+        .map_err(|_| CommandError::invalid_input("Invalid document URI"))?;
+    Ok(PathBuf::from(path))
+}
+
+fn lsp_offset(content: &str, starts: &[usize], position: &serde_json::Value) -> CommandResult<usize> {
+    let invalid = || CommandError::invalid_input("Invalid position in workspace edit");
+    let line = position.get("line").and_then(|v| v.as_u64()).ok_or_else(invalid)? as usize;
+    let character = position.get("character").and_then(|v| v.as_u64()).ok_or_else(invalid)? as usize;
+    let line_start = *starts.get(line).ok_or_else(invalid)?;
+    let line_text = content[line_start..].split('\n').next().unwrap_or("");
+
+    let mut units = 0;
+    for (offset, c) in line_text.char_indices() {
+        if units >= character {
+            return Ok(line_start + offset);
+        }
+        units += c.len_utf16();
+    }
+    Ok(line_start + line_text.len())
+}
+
+fn lsp_edits(edit: &serde_json::Value) -> CommandResult<Vec<(PathBuf, Vec<serde_json::Value>)>> {
+    let mut files = Vec::new();
+    if let Some(changes) = edit.get("changes").and_then(|c| c.as_object()) {
+        for (uri, edits) in changes {
+            files.push((uri_to_path(uri)?, edits.as_array().cloned().unwrap_or_default()));
+        }
+    }
+    if let Some(document_changes) = edit.get("documentChanges").and_then(|c| c.as_array()) {
+        for change in document_changes {
+            let uri = match change.pointer("/textDocument/uri").and_then(|u| u.as_str()) {
+                Some(uri) => uri,
+                None => continue,
+            };
+            let edits = change.get("edits").and_then(|e| e.as_array()).cloned().unwrap_or_default();
+            files.push((uri_to_path(uri)?, edits));
+        }
+    }
+    Ok(files)
+}
+
+fn workspace_edit_edits(edit: &serde_json::Value) -> CommandResult<RawEdits> {
+    let mut edits = RawEdits::new();
+    for (path, text_edits) in lsp_edits(edit)? {
+        let path = ensure_allowed(&path)?;
+        let text = load_text(&path)?;
+        let starts = line_starts(&text.content);
+        for text_edit in text_edits {
+            let start = lsp_offset(&text.content, &starts, &text_edit["range"]["start"])?;
+            let end = lsp_offset(&text.content, &starts, &text_edit["range"]["end"])?;
+            let new_text = text_edit
+                .get("newText")
+                .and_then(|t| t.as_str())
+                .unwrap_or_default()
+                .to_string();
+            edits.entry(path.clone()).or_default().push((start, end, new_text));
+        }
+    }
+    Ok(edits)
+}
+
+#[tauri::command]
+pub fn rename_references_preview(
+    root: &str,
+    old_path: &str,
+    new_path: &str,
+    workspace_edit: Option<serde_json::Value>,
+) -> CommandResult<RenamePreview> {
+    let root = ensure_allowed(root)?;
+    let old = ensure_allowed(old_path)?;
+    let new = ensure_entry_allowed(new_path)?;
+    if fs::symlink_metadata(&new).is_ok() {
+        return Err(CommandError::new(ErrorKind::AlreadyExists, "Destination already exists").with_path(new_path));
+    }
+    let mapping = RenameMapping {
+        is_dir: old.is_dir(),
+        old,
+        new,
+    };
+
+    let (source, raw_edits, truncated) = match workspace_edit.filter(|e| !e.is_null()) {
+        Some(edit) => ("languageServer", workspace_edit_edits(&edit)?, false),
+        None => {
+            let (edits, truncated) = text_search_edits(&root.to_string_lossy(), &mapping)?;
+            ("textSearch", edits, truncated)
+        }
+    };
+
+    let mut files = Vec::new();
+    for (path, mut ranges) in raw_edits {
+        let text = load_text(&path)?;
+        let starts = line_starts(&text.content);
+        ranges.sort_by_key(|(start, end, _)| (*start, *end));
+        let edits = ranges
+            .into_iter()
+            .filter(|(start, end, _)| start <= end && *end <= text.content.len())
+            .map(|(start, end, new_text)| make_edit(&text.content, &starts, start, end, new_text))
+            .collect();
+        files.push(FileReferenceEdits {
+            path: path.to_string_lossy().to_string(),
+            version: text.version,
+            edits,
+        });
+    }
+
+    Ok(RenamePreview {
+        old_path: old_path.to_string(),
+        new_path: new_path.to_string(),
+        source: source.to_string(),
+        files,
+        truncated,
+    })
+}
+
+fn apply_edits(path: &Path, file: &FileReferenceEdits) -> CommandResult<(Vec<u8>, Vec<u8>)> {
+    let text = load_text(path)?;
+    if !text.version.same_content(&file.version) {
+        return Err(CommandError::conflict("File has changed since the preview was created").with_path(path));
+    }
+
+    let mut edits = file.edits.clone();
+    edits.sort_by_key(|e| std::cmp::Reverse(e.start));
+    let mut content = text.content.clone();
+    let mut limit = content.len();
+    for edit in edits {
+        let valid = edit.start <= edit.end
+            && edit.end <= limit
+            && content.is_char_boundary(edit.start)
+            && content.is_char_boundary(edit.end);
+        if !valid || content[edit.start..edit.end] != edit.old_text {
+            return Err(CommandError::invalid_input("Edits overlap or do not match the file").with_path(path));
+        }
+        content.replace_range(edit.start..edit.end, &edit.new_text);
+        limit = edit.start;
+    }
+    Ok((text.content.into_bytes(), content.into_bytes()))
+}
+
+#[tauri::command]
+pub fn rename_with_references(preview: RenamePreview) -> CommandResult<Vec<String>> {
+    let old = Path::new(&preview.old_path);
+    let new = Path::new(&preview.new_path);
+    let mapping = RenameMapping {
+        is_dir: old.is_dir(),
+        old: ensure_allowed(old)?,
+        new: ensure_entry_allowed(new)?,
+    };
+
+    let mut planned = Vec::new();
+    for file in &preview.files {
+        let path = ensure_allowed(&file.path)?;
+        let (original, updated) = apply_edits(&path, file)?;
+        planned.push((path, original, updated));
+    }
+
+    let restore = |written: &[(PathBuf, Vec<u8>, Vec<u8>)], mut error: CommandError| {
+        let failed: Vec<String> = written
+            .iter()
+            .filter_map(|(path, original, _)| atomic_write(path, original).err())
+            .map(|e| e.to_string())
+            .collect();
+        if !failed.is_empty() {
+            error.message = format!("{}; restoring edited files also failed: {}", error.message, failed.join("; "));
+        }
+        error
+    };
+
+    for (index, (path, _, updated)) in planned.iter().enumerate() {
+        if let Err(e) = atomic_write(path, updated) {
+            return Err(restore(&planned[..index], e));
+        }
+    }
+    if let Err(e) = rename_entry(old, new) {
+        return Err(restore(&planned, e));
+    }
+
+    Ok(planned
+        .iter()
+        .map(|(path, _, _)| mapping.moved_location(path).to_string_lossy().to_string())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(old: &str, new: &str, is_dir: bool) -> RenameMapping {
+        RenameMapping {
+            old: PathBuf::from(old),
+            new: PathBuf::from(new),
+            is_dir,
+        }
+    }
+
+    fn rewrite(spec: &str, file_dir: &str, new_file_dir: &str, mapping: &RenameMapping) -> Option<String> {
+        rewrite_spec(spec, Path::new(file_dir), Path::new(new_file_dir), mapping)
+    }
+
+    #[test]
+    fn relative_spec_same_directory() {
+        assert_eq!(relative_spec(Path::new("/ws/src"), Path::new("/ws/src/util.ts")), "./util.ts");
+    }
+
+    #[test]
+    fn relative_spec_parent_and_sibling_directories() {
+        assert_eq!(relative_spec(Path::new("/ws/src/a"), Path::new("/ws/src/util.ts")), "../util.ts");
+        assert_eq!(relative_spec(Path::new("/ws/src/a"), Path::new("/ws/lib/b/util")), "../../lib/b/util");
+        assert_eq!(relative_spec(Path::new("/ws/src"), Path::new("/ws/src/a/b/util")), "./a/b/util");
+    }
+
+    #[test]
+    fn map_renamed_file() {
+        let mapping = mapping("/ws/src/util.ts", "/ws/src/helpers.ts", false);
+        assert_eq!(mapping.map(Path::new("/ws/src/util.ts")), Some(PathBuf::from("/ws/src/helpers.ts")));
+        assert_eq!(mapping.map(Path::new("/ws/src/util")), Some(PathBuf::from("/ws/src/helpers")));
+        assert_eq!(mapping.map(Path::new("/ws/src/util.test.ts")), None);
+        assert_eq!(mapping.map(Path::new("/ws/src/other.ts")), None);
+    }
+
+    #[test]
+    fn map_renamed_folder() {
+        let mapping = mapping("/ws/src/utils", "/ws/src/lib", true);
+        assert_eq!(mapping.map(Path::new("/ws/src/utils")), Some(PathBuf::from("/ws/src/lib")));
+        assert_eq!(mapping.map(Path::new("/ws/src/utils/a/b.ts")), Some(PathBuf::from("/ws/src/lib/a/b.ts")));
+        assert_eq!(mapping.map(Path::new("/ws/src/utilsx/b.ts")), None);
+        assert_eq!(mapping.map(Path::new("/ws/src/utils.ts")), None);
+    }
+
+    #[test]
+    fn rewrite_same_directory_rename() {
+        let mapping = mapping("/ws/src/util.ts", "/ws/src/helpers.ts", false);
+        assert_eq!(rewrite("./util.ts", "/ws/src", "/ws/src", &mapping).as_deref(), Some("./helpers.ts"));
+        assert_eq!(rewrite("./util", "/ws/src", "/ws/src", &mapping).as_deref(), Some("./helpers"));
+        assert_eq!(rewrite("../src/util", "/ws/app", "/ws/app", &mapping).as_deref(), Some("../src/helpers"));
+        assert_eq!(rewrite("./other", "/ws/src", "/ws/src", &mapping), None);
+        assert_eq!(rewrite("util", "/ws/src", "/ws/src", &mapping), None);
+    }
+
+    #[test]
+    fn rewrite_cross_directory_move() {
+        let mapping = mapping("/ws/src/util.ts", "/ws/lib/shared/util.ts", false);
+        assert_eq!(rewrite("./util", "/ws/src", "/ws/src", &mapping).as_deref(), Some("../lib/shared/util"));
+        assert_eq!(rewrite("../src/util.ts", "/ws/app", "/ws/app", &mapping).as_deref(), Some("../lib/shared/util.ts"));
+    }
+
+    #[test]
+    fn rewrite_imports_inside_moved_file() {
+        let mapping = mapping("/ws/src/util.ts", "/ws/lib/util.ts", false);
+        assert_eq!(rewrite("./types", "/ws/src", "/ws/lib", &mapping).as_deref(), Some("../src/types"));
+        assert_eq!(rewrite("../lib/other", "/ws/src", "/ws/lib", &mapping).as_deref(), Some("./other"));
+    }
+
+    #[test]
+    fn rewrite_folder_rename() {
+        let mapping = mapping("/ws/src/utils", "/ws/src/lib", true);
+        assert_eq!(rewrite("./utils/format", "/ws/src", "/ws/src", &mapping).as_deref(), Some("./lib/format"));
+        assert_eq!(rewrite("./utils", "/ws/src", "/ws/src", &mapping).as_deref(), Some("./lib"));
+        assert_eq!(rewrite("../format", "/ws/src/utils/a", "/ws/src/lib/a", &mapping).as_deref(), None);
+        assert_eq!(rewrite("../../app", "/ws/src/utils/a", "/ws/src/lib/a", &mapping), None);
+    }
+
+    #[test]
+    fn uri_to_path_decodes_escapes() {
+        assert_eq!(uri_to_path("file:///ws/my%20file.ts").unwrap(), PathBuf::from("/ws/my file.ts"));
+        assert_eq!(uri_to_path("file:///ws/caf%C3%A9.ts").unwrap(), PathBuf::from("/ws/café.ts"));
+        assert_eq!(uri_to_path("file:///ws/100%.ts").unwrap(), PathBuf::from("/ws/100%.ts"));
+        assert_eq!(uri_to_path("file:///ws/%é.ts").unwrap(), PathBuf::from("/ws/%é.ts"));
+        assert!(uri_to_path("http://example.com/a.ts").is_err());
+    }
+
+    #[test]
+    fn rename_with_references_rewrites_imports_without_journaling() {
+        let dir = std::env::temp_dir().join(format!("litcode-rename-apply-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let dir = crate::commands::workspace::trust_root(&dir).unwrap();
+        fs::write(dir.join("utils.ts"), "export const u = 1;\n").unwrap();
+        fs::write(dir.join("main.ts"), "import { u } from './utils';\n").unwrap();
+        let (old, new) = (dir.join("utils.ts"), dir.join("helpers.ts"));
+
+        let preview = rename_references_preview(
+            dir.to_str().unwrap(),
+            old.to_str().unwrap(),
+            new.to_str().unwrap(),
+            None,
+        )
+        .unwrap();
+        rename_with_references(preview).unwrap();
+
+        assert!(new.exists() && !old.exists());
+        assert_eq!(fs::read_to_string(dir.join("main.ts")).unwrap(), "import { u } from './helpers';\n");
+        let journal = crate::commands::journal::fs_journal();
+        assert!(!journal.undo.iter().any(|op| op.path == old.to_string_lossy()));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,3 +1,5 @@
+use super::error::{CommandError, CommandResult};
+use super::workspace::ensure_allowed;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
@@ -33,7 +35,7 @@ pub struct FileSearchResult {
 const MAX_MATCHES: usize = 1000;
 const MAX_FILE_SIZE: u64 = 1024 * 1024; // 1MB max file size to search
 
-fn should_skip_path(path: &Path) -> bool {
+pub(crate) fn should_skip_path(path: &Path) -> bool {
     let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
     
     if name.starts_with('.') {
@@ -200,7 +202,7 @@ pub fn search_content(
     query: &str,
     case_sensitive: Option<bool>,
     use_regex: Option<bool>,
-) -> Result<SearchResult, String> {
+) -> CommandResult<SearchResult> {
     if query.is_empty() {
         return Ok(SearchResult {
             matches: vec![],
@@ -211,8 +213,10 @@ pub fn search_content(
 
     let root = Path::new(root_path);
     if !root.exists() {
-        return Err("Root path does not exist".to_string());
+        return Err(CommandError::not_found("Root path does not exist").with_path(root));
     }
+    let root = ensure_allowed(root)?;
+    let root = root.as_path();
 
     let case_sensitive = case_sensitive.unwrap_or(false);
     let use_regex = use_regex.unwrap_or(false);
@@ -225,9 +229,7 @@ pub fn search_content(
                 .case_insensitive(true)
                 .build()
         };
-        if let Err(e) = test_regex {
-            return Err(format!("Invalid regex: {}", e));
-        }
+        test_regex?;
     }
 
     let mut matches = Vec::new();
@@ -299,15 +301,17 @@ pub fn search_files(
     root_path: &str,
     query: &str,
     case_sensitive: Option<bool>,
-) -> Result<Vec<FileSearchResult>, String> {
+) -> CommandResult<Vec<FileSearchResult>> {
     if query.is_empty() {
         return Ok(vec![]);
     }
 
     let root = Path::new(root_path);
     if !root.exists() {
-        return Err("Root path does not exist".to_string());
+        return Err(CommandError::not_found("Root path does not exist").with_path(root));
     }
+    let root = ensure_allowed(root)?;
+    let root = root.as_path();
 
     let case_sensitive = case_sensitive.unwrap_or(false);
     let mut results = Vec::new();
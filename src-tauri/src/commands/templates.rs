@@ -0,0 +1,465 @@
+use super::config::litcode_dir;
+use super::error::{CommandError, CommandResult, ErrorKind, ResultExt};
+use super::fs::atomic_write;
+use super::workspace::{ensure_allowed, ensure_entry_allowed};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MANIFEST_FILE: &str = "template.json";
+const FILES_DIR: &str = "files";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TemplateVariable {
+    pub name: String,
+    pub prompt: Option<String>,
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TemplateInfo {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub source: String,
+    pub variables: Vec<TemplateVariable>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateResult {
+    pub created: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct TemplateManifest {
+    name: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    variables: Vec<TemplateVariable>,
+    #[serde(default)]
+    inserts: Vec<TemplateInsert>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TemplateInsert {
+    paths: Vec<String>,
+    content: String,
+    after: Option<String>,
+}
+
+struct Template {
+    info: TemplateInfo,
+    dir: PathBuf,
+    manifest: TemplateManifest,
+}
+
+enum Change {
+    CreateDir(PathBuf),
+    CreateFile(PathBuf, Vec<u8>),
+    Modify(PathBuf, Vec<u8>, Vec<u8>),
+}
+
+fn template_roots(workspace: Option<&str>) -> CommandResult<Vec<(PathBuf, &'static str)>> {
+    let mut roots = vec![(litcode_dir()?.join("templates"), "user")];
+    if let Some(workspace) = workspace {
+        let root = ensure_allowed(workspace)?;
+        roots.push((root.join(".litcode").join("templates"), "workspace"));
+    }
+    Ok(roots)
+}
+
+fn load_template(dir: &Path, source: &str) -> Option<Template> {
+    let id = dir.file_name()?.to_string_lossy().to_string();
+    let (manifest, error) = match fs::read_to_string(dir.join(MANIFEST_FILE)) {
+        Ok(json) => match serde_json::from_str(&json) {
+            Ok(manifest) => (manifest, None),
+            Err(e) => (TemplateManifest::default(), Some(format!("Invalid template manifest: {}", e))),
+        },
+        Err(_) => (TemplateManifest::default(), None),
+    };
+    Some(Template {
+        info: TemplateInfo {
+            name: manifest.name.clone().unwrap_or_else(|| id.clone()),
+            id,
+            description: manifest.description.clone(),
+            source: source.to_string(),
+            variables: manifest.variables.clone(),
+            error,
+        },
+        dir: dir.to_path_buf(),
+        manifest,
+    })
+}
+
+fn load_templates(workspace: Option<&str>) -> CommandResult<Vec<Template>> {
+    let mut templates: Vec<Template> = Vec::new();
+    for (root, source) in template_roots(workspace)? {
+        let entries = match fs::read_dir(&root) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            if !entry.path().is_dir() {
+                continue;
+            }
+            if let Some(template) = load_template(&entry.path(), source) {
+                templates.retain(|t| t.info.id != template.info.id);
+                templates.push(template);
+            }
+        }
+    }
+    templates.sort_by_key(|t| t.info.name.to_lowercase());
+    Ok(templates)
+}
+
+fn civil_date(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn resolve_variables(
+    template: &Template,
+    name: &str,
+    values: HashMap<String, String>,
+) -> CommandResult<HashMap<String, String>> {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let (year, month, day) = civil_date(secs.div_euclid(86400));
+
+    let mut variables = HashMap::new();
+    variables.insert("name".to_string(), name.to_string());
+    variables.insert("date".to_string(), format!("{:04}-{:02}-{:02}", year, month, day));
+    variables.insert("year".to_string(), year.to_string());
+
+    let mut missing = Vec::new();
+    for variable in &template.manifest.variables {
+        match values.get(&variable.name).or(variable.default.as_ref()) {
+            Some(value) => {
+                variables.insert(variable.name.clone(), value.clone());
+            }
+            None => missing.push(variable.name.clone()),
+        }
+    }
+    if !missing.is_empty() {
+        return Err(CommandError::invalid_input(format!("Missing template variables: {}", missing.join(", "))));
+    }
+    Ok(variables)
+}
+
+fn substitute(text: &str, variables: &HashMap<String, String>) -> String {
+    static PLACEHOLDER: OnceLock<Regex> = OnceLock::new();
+    let placeholder = PLACEHOLDER.get_or_init(|| Regex::new(r"\$\{(\w+)\}").unwrap());
+    placeholder
+        .replace_all(text, |caps: &regex::Captures| match variables.get(&caps[1]) {
+            Some(value) => value.clone(),
+            None => caps[0].to_string(),
+        })
+        .into_owned()
+}
+
+fn relative_target(target_dir: &Path, relative: &str) -> CommandResult<PathBuf> {
+    let relative = Path::new(relative);
+    if relative.as_os_str().is_empty() || relative.components().any(|c| !matches!(c, Component::Normal(_))) {
+        return Err(CommandError::invalid_input("Template paths must stay inside the target folder")
+            .with_path(relative));
+    }
+    ensure_entry_allowed(target_dir.join(relative))
+}
+
+fn collect_files(dir: &Path, prefix: &Path, files: &mut Vec<(PathBuf, bool)>) -> CommandResult<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir).with_path(dir)?.flatten().collect();
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let relative = prefix.join(entry.file_name());
+        let is_dir = entry.file_type().with_path(entry.path())?.is_dir();
+        files.push((relative.clone(), is_dir));
+        if is_dir {
+            collect_files(&entry.path(), &relative, files)?;
+        }
+    }
+    Ok(())
+}
+
+fn insert_content(existing: &str, content: &str, after: Option<&Regex>) -> Option<String> {
+    if content.trim().is_empty() || existing.contains(content.trim()) {
+        return None;
+    }
+    let block = if content.ends_with('\n') { content.to_string() } else { format!("{}\n", content) };
+
+    let anchor = after.and_then(|regex| {
+        let mut offset = 0;
+        let mut found = None;
+        for line in existing.split_inclusive('\n') {
+            offset += line.len();
+            if regex.is_match(line.trim_end()) {
+                found = Some(offset);
+            }
+        }
+        found
+    });
+
+    let mut updated = existing.to_string();
+    match anchor {
+        Some(offset) => {
+            if !updated[..offset].ends_with('\n') {
+                updated.insert(offset, '\n');
+                updated.insert_str(offset + 1, &block);
+            } else {
+                updated.insert_str(offset, &block);
+            }
+        }
+        None => {
+            if !updated.is_empty() && !updated.ends_with('\n') {
+                updated.push('\n');
+            }
+            updated.push_str(&block);
+        }
+    }
+    Some(updated)
+}
+
+fn plan(template: &Template, target_dir: &Path, variables: &HashMap<String, String>) -> CommandResult<Vec<Change>> {
+    let mut changes = Vec::new();
+
+    let files_dir = template.dir.join(FILES_DIR);
+    let mut files = Vec::new();
+    if files_dir.is_dir() {
+        collect_files(&files_dir, Path::new(""), &mut files)?;
+    }
+    for (relative, is_dir) in files {
+        let target_relative = substitute(&relative.to_string_lossy(), variables);
+        let target = relative_target(target_dir, &target_relative)?;
+        if is_dir {
+            if !target.is_dir() {
+                changes.push(Change::CreateDir(target));
+            }
+            continue;
+        }
+        if fs::symlink_metadata(&target).is_ok() {
+            return Err(CommandError::new(ErrorKind::AlreadyExists, "File already exists").with_path(&target));
+        }
+        let source = files_dir.join(&relative);
+        let bytes = fs::read(&source).with_path(&source)?;
+        let bytes = match String::from_utf8(bytes) {
+            Ok(text) => substitute(&text, variables).into_bytes(),
+            Err(e) => e.into_bytes(),
+        };
+        changes.push(Change::CreateFile(target, bytes));
+    }
+
+    for insert in &template.manifest.inserts {
+        let after = match &insert.after {
+            Some(pattern) => Some(Regex::new(pattern).map_err(|e| {
+                CommandError::new(ErrorKind::InvalidRegex, format!("Invalid template insert pattern: {}", e))
+            })?),
+            None => None,
+        };
+        let mut candidates = Vec::new();
+        for path in &insert.paths {
+            candidates.push(relative_target(target_dir, &substitute(path, variables))?);
+        }
+        let target = match candidates.into_iter().find(|c| c.is_file()) {
+            Some(target) => target,
+            None => continue,
+        };
+        let previous = fs::read(&target).with_path(&target)?;
+        let existing = String::from_utf8(previous.clone())
+            .map_err(|_| CommandError::invalid_input("File is not valid UTF-8").with_path(&target))?;
+        if let Some(updated) = insert_content(&existing, &substitute(&insert.content, variables), after.as_ref()) {
+            changes.push(Change::Modify(target, updated.into_bytes(), previous));
+        }
+    }
+
+    Ok(changes)
+}
+
+fn rollback(applied: &[Change], mut error: CommandError) -> CommandError {
+    let failed: Vec<String> = applied
+        .iter()
+        .rev()
+        .filter_map(|change| {
+            match change {
+                Change::CreateDir(path) => fs::remove_dir(path).with_path(path),
+                Change::CreateFile(path, _) => fs::remove_file(path).with_path(path),
+                Change::Modify(path, _, previous) => atomic_write(path, previous),
+            }
+            .err()
+        })
+        .map(|e| e.to_string())
+        .collect();
+    if !failed.is_empty() {
+        error.message = format!("{}; rolling back the template also failed: {}", error.message, failed.join("; "));
+    }
+    error
+}
+
+fn apply_change(change: &Change) -> CommandResult<()> {
+    match change {
+        Change::CreateDir(path) => fs::create_dir(path).with_path(path),
+        Change::CreateFile(path, bytes) => {
+            let mut file = fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(path)
+                .with_path(path)?;
+            file.write_all(bytes).with_path(path)
+        }
+        Change::Modify(path, bytes, _) => atomic_write(path, bytes),
+    }
+}
+
+fn change_path(change: &Change) -> String {
+    match change {
+        Change::CreateDir(path) | Change::CreateFile(path, _) | Change::Modify(path, _, _) => {
+            path.to_string_lossy().to_string()
+        }
+    }
+}
+
+#[tauri::command]
+pub fn template_list(workspace: Option<String>) -> CommandResult<Vec<TemplateInfo>> {
+    Ok(load_templates(workspace.as_deref())?
+        .into_iter()
+        .map(|t| t.info)
+        .collect())
+}
+
+#[tauri::command]
+pub fn template_apply(
+    workspace: Option<String>,
+    template_id: &str,
+    target_dir: &str,
+    name: &str,
+    variables: Option<HashMap<String, String>>,
+) -> CommandResult<TemplateResult> {
+    let target_dir = ensure_allowed(target_dir)?;
+    if !target_dir.is_dir() {
+        return Err(CommandError::invalid_input("Target must be a directory").with_path(&target_dir));
+    }
+    let template = load_templates(workspace.as_deref())?
+        .into_iter()
+        .find(|t| t.info.id == template_id)
+        .ok_or_else(|| CommandError::not_found(format!("Template '{}' not found", template_id)))?;
+    if let Some(error) = &template.info.error {
+        return Err(CommandError::invalid_input(error.clone()).with_path(template.dir.join(MANIFEST_FILE)));
+    }
+
+    let variables = resolve_variables(&template, name, variables.unwrap_or_default())?;
+    let changes = plan(&template, &target_dir, &variables)?;
+
+    let mut result = TemplateResult {
+        created: Vec::new(),
+        modified: Vec::new(),
+    };
+    for (index, change) in changes.iter().enumerate() {
+        if let Err(e) = apply_change(change) {
+            return Err(rollback(&changes[..index], e));
+        }
+        match change {
+            Change::CreateFile(..) | Change::CreateDir(_) => result.created.push(change_path(change)),
+            Change::Modify(..) => result.modified.push(change_path(change)),
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::workspace::trust_root;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("litcode-templates-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        trust_root(&dir).unwrap()
+    }
+
+    fn write_template(workspace: &Path, id: &str, manifest: &str, files: &[(&str, &str)]) {
+        let dir = workspace.join(".litcode/templates").join(id);
+        fs::create_dir_all(dir.join(FILES_DIR)).unwrap();
+        fs::write(dir.join(MANIFEST_FILE), manifest).unwrap();
+        for (path, content) in files {
+            let path = dir.join(FILES_DIR).join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+    }
+
+    #[test]
+    fn apply_substitutes_variables_and_inserts_after_anchor() {
+        let dir = scratch_dir("apply");
+        let manifest = r#"{"variables":[{"name":"kind","default":"struct"}],
+            "inserts":[{"paths":["lib.rs"],"content":"pub mod ${name};","after":"^mod "}]}"#;
+        write_template(&dir, "module", manifest, &[("${name}.rs", "pub ${kind} ${name} {}\n")]);
+        fs::write(dir.join("lib.rs"), "mod a;\n\nfn main() {}\n").unwrap();
+        let workspace = Some(dir.to_string_lossy().to_string());
+
+        let result = template_apply(workspace, "module", dir.to_str().unwrap(), "foo", None).unwrap();
+
+        assert_eq!(result.created, vec![dir.join("foo.rs").to_string_lossy().to_string()]);
+        assert_eq!(fs::read_to_string(dir.join("foo.rs")).unwrap(), "pub struct foo {}\n");
+        assert_eq!(fs::read_to_string(dir.join("lib.rs")).unwrap(), "mod a;\npub mod foo;\n\nfn main() {}\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_manifest_is_listed_with_error_and_refused() {
+        let dir = scratch_dir("invalid");
+        write_template(&dir, "broken", "{ not json", &[("a.txt", "")]);
+        let workspace = Some(dir.to_string_lossy().to_string());
+
+        let listed = template_list(workspace.clone()).unwrap();
+        let broken = listed.iter().find(|t| t.id == "broken").unwrap();
+        assert!(broken.error.as_deref().is_some_and(|e| e.starts_with("Invalid template manifest")));
+
+        let err = template_apply(workspace, "broken", dir.to_str().unwrap(), "x", None).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::InvalidInput);
+        assert!(!dir.join("a.txt").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rollback_reports_changes_it_could_not_undo() {
+        let dir = scratch_dir("rollback");
+        write_template(&dir, "pair", "{}", &[("${name}/a.txt", "a"), ("${name}/b.txt", "b")]);
+        let workspace = Some(dir.to_string_lossy().to_string());
+        let target = dir.join("target");
+        fs::create_dir_all(&target).unwrap();
+        let changes = vec![
+            Change::CreateDir(target.join("x")),
+            Change::CreateFile(target.join("x/a.txt"), b"a".to_vec()),
+        ];
+        for change in &changes {
+            apply_change(change).unwrap();
+        }
+
+        let err = rollback(&changes, CommandError::other("boom"));
+        assert_eq!(err.message, "boom");
+        assert!(!target.join("x").exists());
+
+        let err = rollback(&changes, CommandError::other("boom"));
+        assert!(err.message.starts_with("boom; rolling back the template also failed"));
+
+        assert!(template_apply(workspace.clone(), "pair", target.to_str().unwrap(), "../escape", None).is_err());
+        assert!(!dir.join("escape").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
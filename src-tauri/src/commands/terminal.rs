@@ -1,3 +1,4 @@
+use super::error::{CommandError, CommandResult};
 use portable_pty::{native_pty_system, CommandBuilder, MasterPty, PtySize};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -48,7 +49,7 @@ pub async fn terminal_spawn(
     cwd: Option<String>,
     rows: Option<u16>,
     cols: Option<u16>,
-) -> Result<TerminalSpawnResult, String> {
+) -> CommandResult<TerminalSpawnResult> {
     let id = generate_terminal_id();
     let rows = rows.unwrap_or(24);
     let cols = cols.unwrap_or(80);
@@ -61,7 +62,7 @@ pub async fn terminal_spawn(
             pixel_width: 0,
             pixel_height: 0,
         })
-        .map_err(|e| format!("Failed to open PTY: {}", e))?;
+        .map_err(|e| CommandError::other(format!("Failed to open PTY: {}", e)))?;
 
     let shell = get_default_shell();
     let mut cmd = CommandBuilder::new(&shell);
@@ -88,17 +89,17 @@ pub async fn terminal_spawn(
     let _child = pty_pair
         .slave
         .spawn_command(cmd)
-        .map_err(|e| format!("Failed to spawn shell: {}", e))?;
+        .map_err(|e| CommandError::other(format!("Failed to spawn shell: {}", e)))?;
 
     let writer = pty_pair
         .master
         .take_writer()
-        .map_err(|e| format!("Failed to get PTY writer: {}", e))?;
+        .map_err(|e| CommandError::other(format!("Failed to get PTY writer: {}", e)))?;
 
     let reader = pty_pair
         .master
         .try_clone_reader()
-        .map_err(|e| format!("Failed to get PTY reader: {}", e))?;
+        .map_err(|e| CommandError::other(format!("Failed to get PTY reader: {}", e)))?;
 
     let terminal_map = get_terminal_map(&app);
     {
@@ -147,7 +148,7 @@ fn read_terminal_output(app: AppHandle, id: String, mut reader: Box<dyn Read + S
 }
 
 #[tauri::command]
-pub async fn terminal_write(app: AppHandle, id: String, data: Vec<u8>) -> Result<(), String> {
+pub async fn terminal_write(app: AppHandle, id: String, data: Vec<u8>) -> CommandResult<()> {
     let terminal_map = get_terminal_map(&app);
     let mut map = terminal_map.lock().unwrap();
 
@@ -155,14 +156,14 @@ pub async fn terminal_write(app: AppHandle, id: String, data: Vec<u8>) -> Result
         terminal
             .writer
             .write_all(&data)
-            .map_err(|e| format!("Write failed: {}", e))?;
+            .map_err(|e| CommandError::other(format!("Write failed: {}", e)))?;
         terminal
             .writer
             .flush()
-            .map_err(|e| format!("Flush failed: {}", e))?;
+            .map_err(|e| CommandError::other(format!("Flush failed: {}", e)))?;
         Ok(())
     } else {
-        Err(format!("Terminal {} not found", id))
+        Err(CommandError::not_found(format!("Terminal {} not found", id)))
     }
 }
 
@@ -172,7 +173,7 @@ pub async fn terminal_resize(
     id: String,
     rows: u16,
     cols: u16,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let terminal_map = get_terminal_map(&app);
     let map = terminal_map.lock().unwrap();
 
@@ -185,15 +186,15 @@ pub async fn terminal_resize(
                 pixel_width: 0,
                 pixel_height: 0,
             })
-            .map_err(|e| format!("Resize failed: {}", e))?;
+            .map_err(|e| CommandError::other(format!("Resize failed: {}", e)))?;
         Ok(())
     } else {
-        Err(format!("Terminal {} not found", id))
+        Err(CommandError::not_found(format!("Terminal {} not found", id)))
     }
 }
 
 #[tauri::command]
-pub async fn terminal_kill(app: AppHandle, id: String) -> Result<(), String> {
+pub async fn terminal_kill(app: AppHandle, id: String) -> CommandResult<()> {
     let terminal_map = get_terminal_map(&app);
     let mut map = terminal_map.lock().unwrap();
     map.remove(&id);
@@ -0,0 +1,429 @@
+use super::error::{CommandError, CommandResult, ErrorKind, ResultExt};
+use super::fs::{copy_recursive, is_same_entry, move_path, remove_path_permanently, temp_sibling_path};
+use super::journal;
+use super::trash::trash_entry;
+use super::workspace::ensure_entry_allowed;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    #[default]
+    Error,
+    Overwrite,
+    Skip,
+    Rename,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransferEntry {
+    pub source: String,
+    pub destination: String,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransferResult {
+    #[serde(rename = "operationId")]
+    pub operation_id: String,
+    pub entries: Vec<TransferEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransferProgress {
+    #[serde(rename = "operationId")]
+    pub operation_id: String,
+    #[serde(rename = "processedBytes")]
+    pub processed_bytes: u64,
+    #[serde(rename = "totalBytes")]
+    pub total_bytes: u64,
+    #[serde(rename = "completedItems")]
+    pub completed_items: usize,
+    #[serde(rename = "totalItems")]
+    pub total_items: usize,
+    #[serde(rename = "currentPath")]
+    pub current_path: Option<String>,
+    pub done: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TransferKind {
+    Copy,
+    Move,
+}
+
+pub(crate) fn generate_operation_id(prefix: &str) -> String {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64;
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    format!("{}-{:x}-{:x}", prefix, timestamp, count)
+}
+
+fn total_size(path: &Path) -> u64 {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(m) => m,
+        Err(_) => return 0,
+    };
+    if metadata.is_dir() {
+        fs::read_dir(path)
+            .map(|entries| entries.flatten().map(|e| total_size(&e.path())).sum())
+            .unwrap_or(0)
+    } else if metadata.is_file() {
+        metadata.len()
+    } else {
+        0
+    }
+}
+
+fn split_name(path: &Path) -> (String, String) {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if path.is_dir() {
+        return (name, String::new());
+    }
+    match name.rfind('.') {
+        Some(index) if index > 0 => (name[..index].to_string(), name[index..].to_string()),
+        _ => (name, String::new()),
+    }
+}
+
+fn numbered_path(target: &Path, label: impl Fn(&str, usize) -> String) -> PathBuf {
+    let (stem, ext) = split_name(target);
+    let parent = target.parent().unwrap_or(Path::new(""));
+    (1..)
+        .map(|n| parent.join(format!("{}{}", label(&stem, n), ext)))
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .unwrap_or_else(|| target.to_path_buf())
+}
+
+pub(crate) fn conflict_free_path(target: &Path) -> PathBuf {
+    numbered_path(target, |stem, n| format!("{} ({})", stem, n))
+}
+
+fn duplicate_target(source: &Path) -> PathBuf {
+    numbered_path(source, |stem, n| {
+        if n == 1 {
+            format!("{} copy", stem)
+        } else {
+            format!("{} copy {}", stem, n)
+        }
+    })
+}
+
+pub(crate) struct ProgressReporter {
+    app: AppHandle,
+    progress: TransferProgress,
+    last_emit: Instant,
+}
+
+impl ProgressReporter {
+    pub(crate) fn new(app: AppHandle, operation_id: String, total_bytes: u64, total_items: usize) -> Self {
+        ProgressReporter {
+            app,
+            progress: TransferProgress {
+                operation_id,
+                processed_bytes: 0,
+                total_bytes,
+                completed_items: 0,
+                total_items,
+                current_path: None,
+                done: false,
+            },
+            last_emit: Instant::now(),
+        }
+    }
+
+    pub(crate) fn advance(&mut self, path: &Path, bytes: u64) {
+        self.progress.processed_bytes += bytes;
+        if self.last_emit.elapsed() >= PROGRESS_INTERVAL {
+            self.progress.current_path = Some(path.to_string_lossy().to_string());
+            self.emit();
+        }
+    }
+
+    pub(crate) fn complete_item(&mut self, bytes: u64) {
+        self.progress.completed_items += 1;
+        self.progress.processed_bytes = self.progress.processed_bytes.max(bytes);
+        self.emit();
+    }
+
+    pub(crate) fn finish(&mut self) {
+        self.progress.done = true;
+        self.progress.current_path = None;
+        self.emit();
+    }
+
+    fn emit(&mut self) {
+        self.last_emit = Instant::now();
+        let _ = self.app.emit("fs:transfer-progress", self.progress.clone());
+    }
+}
+
+fn allowed_source(source: &Path) -> CommandResult<PathBuf> {
+    fs::symlink_metadata(source).with_path(source)?;
+    ensure_entry_allowed(source)
+}
+
+fn transfer_one(
+    kind: TransferKind,
+    source: &Path,
+    destination: PathBuf,
+    policy: ConflictPolicy,
+    reporter: &mut ProgressReporter,
+) -> CommandResult<TransferEntry> {
+    let source = &allowed_source(source)?;
+    let destination = ensure_entry_allowed(&destination)?;
+
+    if source.is_dir() {
+        let source_abs = source.canonicalize().with_path(source)?;
+        let dest_parent = destination
+            .parent()
+            .and_then(|p| p.canonicalize().ok())
+            .unwrap_or_default();
+        if dest_parent.starts_with(&source_abs) {
+            return Err(CommandError::invalid_input("Cannot copy or move a folder into itself")
+                .with_path(source));
+        }
+    }
+
+    let entry = |destination: &Path, status: &str| TransferEntry {
+        source: source.to_string_lossy().to_string(),
+        destination: destination.to_string_lossy().to_string(),
+        status: status.to_string(),
+    };
+
+    let mut destination = destination;
+    let mut replacing = false;
+    if fs::symlink_metadata(&destination).is_ok() {
+        let same = is_same_entry(source, &destination);
+        match (policy, kind, same) {
+            (_, TransferKind::Move, true) => return Ok(entry(&destination, "skipped")),
+            (_, TransferKind::Copy, true) | (ConflictPolicy::Rename, _, _) => {
+                destination = conflict_free_path(&destination);
+            }
+            (ConflictPolicy::Skip, _, _) => return Ok(entry(&destination, "skipped")),
+            (ConflictPolicy::Overwrite, _, _) => replacing = true,
+            (ConflictPolicy::Error, _, _) => {
+                return Err(
+                    CommandError::new(ErrorKind::AlreadyExists, "Destination already exists")
+                        .with_path(&destination),
+                );
+            }
+        }
+    }
+
+    let staging = if kind == TransferKind::Copy || replacing {
+        temp_sibling_path(&destination)
+    } else {
+        destination.clone()
+    };
+    let start = reporter.progress.processed_bytes;
+    let size = total_size(source);
+    let mut advance = |path: &Path, bytes: u64| reporter.advance(path, bytes);
+    let (result, status) = match kind {
+        TransferKind::Copy => (copy_recursive(source, &staging, &mut advance), "copied"),
+        TransferKind::Move => (move_path(source, &staging, &mut advance), "moved"),
+    };
+    if let Err(e) = result {
+        if staging != destination {
+            let _ = remove_path_permanently(&staging);
+        }
+        return Err(CommandError::from(e).with_path(source));
+    }
+
+    let undo_staging = |staging: &Path| match kind {
+        TransferKind::Copy => remove_path_permanently(staging),
+        TransferKind::Move => fs::rename(staging, source),
+    };
+    if replacing {
+        let before = if kind == TransferKind::Move { journal::fingerprint(&destination) } else { None };
+        let swapped = trash_entry(&destination).and_then(|item| {
+            fs::rename(&staging, &destination).with_path(&destination)?;
+            Ok(item)
+        });
+        match swapped {
+            Ok(item) if kind == TransferKind::Move => journal::record_delete(&destination, &item.id, before),
+            Ok(_) => {}
+            Err(e) => {
+                let _ = undo_staging(&staging);
+                return Err(e);
+            }
+        }
+    } else if staging != destination {
+        let placed = if fs::symlink_metadata(&destination).is_ok() {
+            Err(CommandError::new(ErrorKind::AlreadyExists, "Destination already exists").with_path(&destination))
+        } else {
+            fs::rename(&staging, &destination).with_path(&destination)
+        };
+        if let Err(e) = placed {
+            let _ = undo_staging(&staging);
+            return Err(e);
+        }
+    }
+    if kind == TransferKind::Move {
+        journal::record_rename(source, &destination);
+    }
+
+    reporter.complete_item(start + size);
+    Ok(entry(&destination, status))
+}
+
+fn run_transfer(
+    app: AppHandle,
+    kind: TransferKind,
+    sources: Vec<String>,
+    destination_dir: String,
+    policy: ConflictPolicy,
+    operation_id: String,
+) -> CommandResult<TransferResult> {
+    let destination_dir = ensure_entry_allowed(Path::new(&destination_dir))?;
+    if !destination_dir.is_dir() {
+        return Err(CommandError::invalid_input("Destination must be a directory").with_path(&destination_dir));
+    }
+    let sources = sources
+        .iter()
+        .map(|source| allowed_source(Path::new(source)))
+        .collect::<CommandResult<Vec<_>>>()?;
+
+    let total_bytes = sources.iter().map(|s| total_size(s)).sum();
+    let mut reporter = ProgressReporter::new(app, operation_id.clone(), total_bytes, sources.len());
+    let mut entries = Vec::new();
+
+    let result: CommandResult<()> = sources.iter().try_for_each(|source| {
+        let name = source
+            .file_name()
+            .ok_or_else(|| CommandError::invalid_input("Invalid source path").with_path(source))?;
+        let entry = transfer_one(kind, source, destination_dir.join(name), policy, &mut reporter)?;
+        entries.push(entry);
+        Ok(())
+    });
+    reporter.finish();
+    result?;
+
+    Ok(TransferResult {
+        operation_id,
+        entries,
+    })
+}
+
+async fn spawn_transfer(
+    app: AppHandle,
+    kind: TransferKind,
+    sources: Vec<String>,
+    destination_dir: String,
+    policy: Option<ConflictPolicy>,
+    operation_id: Option<String>,
+) -> CommandResult<TransferResult> {
+    let operation_id = operation_id.unwrap_or_else(|| generate_operation_id("transfer"));
+    let policy = policy.unwrap_or_default();
+    tokio::task::spawn_blocking(move || {
+        run_transfer(app, kind, sources, destination_dir, policy, operation_id)
+    })
+    .await
+    .map_err(|e| CommandError::other(format!("Transfer failed: {}", e)))?
+}
+
+#[tauri::command]
+pub async fn copy_paths(
+    app: AppHandle,
+    sources: Vec<String>,
+    destination_dir: String,
+    policy: Option<ConflictPolicy>,
+    operation_id: Option<String>,
+) -> CommandResult<TransferResult> {
+    spawn_transfer(app, TransferKind::Copy, sources, destination_dir, policy, operation_id).await
+}
+
+#[tauri::command]
+pub async fn move_paths(
+    app: AppHandle,
+    sources: Vec<String>,
+    destination_dir: String,
+    policy: Option<ConflictPolicy>,
+    operation_id: Option<String>,
+) -> CommandResult<TransferResult> {
+    spawn_transfer(app, TransferKind::Move, sources, destination_dir, policy, operation_id).await
+}
+
+#[tauri::command]
+pub async fn duplicate_path(
+    app: AppHandle,
+    path: String,
+    operation_id: Option<String>,
+) -> CommandResult<TransferEntry> {
+    let operation_id = operation_id.unwrap_or_else(|| generate_operation_id("transfer"));
+    tokio::task::spawn_blocking(move || {
+        let source = allowed_source(Path::new(&path))?;
+        let destination = duplicate_target(&source);
+        let mut reporter = ProgressReporter::new(app, operation_id, total_size(&source), 1);
+        let result = transfer_one(
+            TransferKind::Copy,
+            &source,
+            destination,
+            ConflictPolicy::Error,
+            &mut reporter,
+        );
+        reporter.finish();
+        result
+    })
+    .await
+    .map_err(|e| CommandError::other(format!("Duplicate failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::workspace::trust_root;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("litcode-transfer-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        trust_root(&dir).unwrap()
+    }
+
+    #[test]
+    fn numbered_paths_skip_existing_entries() {
+        let dir = scratch_dir("numbered");
+        fs::write(dir.join("notes.txt"), "").unwrap();
+        fs::write(dir.join("notes (1).txt"), "").unwrap();
+        fs::create_dir(dir.join("src.d")).unwrap();
+
+        assert_eq!(conflict_free_path(&dir.join("notes.txt")), dir.join("notes (2).txt"));
+        assert_eq!(duplicate_target(&dir.join("notes.txt")), dir.join("notes copy.txt"));
+        assert_eq!(duplicate_target(&dir.join("src.d")), dir.join("src.d copy"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn sources_are_validated_before_they_are_walked() {
+        let dir = scratch_dir("validate");
+        let outside = std::env::temp_dir().join(format!("litcode-transfer-outside-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&outside);
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(dir.join("a.txt"), "abc").unwrap();
+
+        assert!(allowed_source(&outside).is_err());
+        assert_eq!(allowed_source(&dir.join("missing")).unwrap_err().kind, ErrorKind::NotFound);
+        let source = allowed_source(&dir.join("a.txt")).unwrap();
+        assert_eq!(total_size(&source), 3);
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&outside).unwrap();
+    }
+}
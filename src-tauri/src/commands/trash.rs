@@ -0,0 +1,431 @@
+use super::config::litcode_dir;
+use super::error::{CommandError, CommandResult, ErrorKind, ResultExt};
+use super::fs::{move_path, remove_path_permanently};
+use super::history::record_deletion;
+use super::journal;
+use super::workspace::ensure_entry_allowed;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const LITCODE_TRASH_PREFIX: &str = "litcode-";
+const INFO_FILE: &str = "info.json";
+const ITEM_DIR: &str = "item";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrashedItem {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "originalPath")]
+    pub original_path: String,
+    #[serde(rename = "deletedAt")]
+    pub deleted_at: i64,
+    pub location: String,
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+fn litcode_trash_dir() -> CommandResult<PathBuf> {
+    Ok(litcode_dir()?.join("trash"))
+}
+
+fn generate_trash_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64;
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    format!("{}{:x}-{:x}", LITCODE_TRASH_PREFIX, timestamp, count)
+}
+
+pub(crate) fn move_to_litcode_trash(path: &Path) -> CommandResult<TrashedItem> {
+    let id = generate_trash_id();
+    let entry_dir = litcode_trash_dir()?.join(&id);
+    fs::create_dir_all(&entry_dir).with_path(&entry_dir)?;
+
+    let item = TrashedItem {
+        id,
+        name: path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        original_path: path.to_string_lossy().to_string(),
+        deleted_at: now_secs(),
+        location: "litcode".to_string(),
+    };
+
+    let info = serde_json::to_string_pretty(&item).map_err(|e| CommandError::other(e.to_string()))?;
+    fs::write(entry_dir.join(INFO_FILE), info).with_path(&entry_dir)?;
+
+    if let Err(e) = move_path(path, &entry_dir.join(ITEM_DIR), &mut |_, _| {}) {
+        let _ = fs::remove_dir_all(&entry_dir);
+        return Err(CommandError::from(e).with_path(path));
+    }
+
+    Ok(item)
+}
+
+fn list_litcode_trash() -> CommandResult<Vec<TrashedItem>> {
+    let dir = litcode_trash_dir()?;
+    let read_dir = match fs::read_dir(&dir) {
+        Ok(read_dir) => read_dir,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(CommandError::from(e).with_path(&dir)),
+    };
+
+    let mut items = Vec::new();
+    for entry in read_dir.flatten() {
+        let info = match fs::read_to_string(entry.path().join(INFO_FILE)) {
+            Ok(info) => info,
+            Err(_) => continue,
+        };
+        if let Ok(item) = serde_json::from_str::<TrashedItem>(&info) {
+            items.push(item);
+        }
+    }
+    Ok(items)
+}
+
+fn restore_from_litcode_trash(id: &str) -> CommandResult<TrashedItem> {
+    let entry_dir = litcode_trash_dir()?.join(id);
+    let info = fs::read_to_string(entry_dir.join(INFO_FILE)).with_path(&entry_dir)?;
+    let item: TrashedItem = serde_json::from_str(&info).map_err(|e| CommandError::other(e.to_string()))?;
+
+    let original = Path::new(&item.original_path);
+    let original = &ensure_entry_allowed(original)?;
+    if fs::symlink_metadata(original).is_ok() {
+        return Err(CommandError::new(
+            ErrorKind::AlreadyExists,
+            "A file already exists at the original location",
+        )
+        .with_path(original));
+    }
+    if let Some(parent) = original.parent() {
+        fs::create_dir_all(parent).with_path(parent)?;
+    }
+
+    move_path(&entry_dir.join(ITEM_DIR), original, &mut |_, _| {}).with_path(original)?;
+    let _ = fs::remove_dir_all(&entry_dir);
+    Ok(item)
+}
+
+#[cfg(any(
+    target_os = "windows",
+    all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+))]
+mod system {
+    use super::TrashedItem;
+    use crate::commands::error::{CommandError, CommandResult, ErrorKind};
+    use std::path::Path;
+    use std::time::SystemTime;
+
+    pub const SUPPORTED: bool = true;
+
+    fn to_trashed_item(item: &trash::TrashItem) -> TrashedItem {
+        TrashedItem {
+            id: item.id.to_string_lossy().to_string(),
+            name: item.name.to_string_lossy().to_string(),
+            original_path: item.original_path().to_string_lossy().to_string(),
+            deleted_at: item.time_deleted,
+            location: "system".to_string(),
+        }
+    }
+
+    fn map_error(e: trash::Error) -> CommandError {
+        match e {
+            trash::Error::RestoreCollision { path, .. } => CommandError::new(
+                ErrorKind::AlreadyExists,
+                "A file already exists at the original location",
+            )
+            .with_path(path),
+            e => CommandError::other(format!("Trash error: {}", e)),
+        }
+    }
+
+    pub fn list() -> CommandResult<Vec<TrashedItem>> {
+        let items = trash::os_limited::list().map_err(map_error)?;
+        Ok(items.iter().map(to_trashed_item).collect())
+    }
+
+    #[cfg(unix)]
+    pub fn find_deleted(path: &Path, since: SystemTime) -> Option<TrashedItem> {
+        use std::fs;
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let name = path.file_name()?.to_string_lossy().to_string();
+        let since = since.checked_sub(Duration::from_secs(1)).unwrap_or(UNIX_EPOCH);
+        let mut newest: Option<(SystemTime, std::path::PathBuf)> = None;
+        for folder in trash::os_limited::trash_folders().ok()? {
+            let entries = match fs::read_dir(folder.join("info")) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+            for entry in entries.flatten() {
+                if !is_info_file_for(&entry.file_name().to_string_lossy(), &name) {
+                    continue;
+                }
+                let modified = match entry.metadata().and_then(|m| m.modified()) {
+                    Ok(modified) if modified >= since => modified,
+                    _ => continue,
+                };
+                if newest.as_ref().is_some_and(|(time, _)| *time >= modified) {
+                    continue;
+                }
+                if info_refers_to(&entry.path(), path) {
+                    newest = Some((modified, entry.path()));
+                }
+            }
+        }
+
+        let (modified, info_path) = newest?;
+        Some(TrashedItem {
+            id: info_path.to_string_lossy().to_string(),
+            name,
+            original_path: path.to_string_lossy().to_string(),
+            deleted_at: modified.duration_since(UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or(0),
+            location: "system".to_string(),
+        })
+    }
+
+    #[cfg(unix)]
+    pub(super) fn is_info_file_for(file_name: &str, name: &str) -> bool {
+        match file_name.strip_suffix(".trashinfo").and_then(|stem| stem.strip_prefix(name)) {
+            Some("") => true,
+            Some(suffix) => suffix
+                .strip_prefix('.')
+                .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit())),
+            None => false,
+        }
+    }
+
+    #[cfg(unix)]
+    pub(super) fn info_refers_to(info_path: &Path, path: &Path) -> bool {
+        use std::os::unix::ffi::OsStringExt;
+
+        let info = match std::fs::read_to_string(info_path) {
+            Ok(info) => info,
+            Err(_) => return false,
+        };
+        info.lines()
+            .filter_map(|line| line.strip_prefix("Path="))
+            .map(|value| {
+                let decoded = crate::commands::util::percent_decode(value.trim());
+                std::path::PathBuf::from(std::ffi::OsString::from_vec(decoded))
+            })
+            .any(|original| original == path || (original.is_relative() && path.ends_with(&original)))
+    }
+
+    #[cfg(windows)]
+    const DEFERRED_PREFIX: &str = "deleted:";
+
+    #[cfg(windows)]
+    pub fn find_deleted(path: &Path, since: SystemTime) -> Option<TrashedItem> {
+        let deleted_at = since
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        Some(TrashedItem {
+            id: format!("{}{}:{}", DEFERRED_PREFIX, deleted_at, path.to_string_lossy()),
+            name: path.file_name()?.to_string_lossy().to_string(),
+            original_path: path.to_string_lossy().to_string(),
+            deleted_at,
+            location: "system".to_string(),
+        })
+    }
+
+    #[cfg(windows)]
+    fn locate(items: Vec<trash::TrashItem>, id: &str) -> Option<trash::TrashItem> {
+        let (deleted_at, path) = match id.strip_prefix(DEFERRED_PREFIX).and_then(|rest| rest.split_once(':')) {
+            Some((deleted_at, path)) => (deleted_at.parse::<i64>().ok()?, Path::new(path)),
+            None => return items.into_iter().find(|item| item.id.to_string_lossy() == id),
+        };
+        items
+            .into_iter()
+            .filter(|item| item.original_path() == path && item.time_deleted >= deleted_at - 1)
+            .min_by_key(|item| item.time_deleted)
+    }
+
+    #[cfg(unix)]
+    fn locate(items: Vec<trash::TrashItem>, id: &str) -> Option<trash::TrashItem> {
+        items.into_iter().find(|item| item.id.to_string_lossy() == id)
+    }
+
+    pub fn restore(id: &str) -> CommandResult<TrashedItem> {
+        let not_found = || CommandError::not_found("Item is no longer in the trash");
+        if id.is_empty() {
+            return Err(not_found());
+        }
+        let items = trash::os_limited::list().map_err(map_error)?;
+        let item = locate(items, id).ok_or_else(not_found)?;
+        let restored = to_trashed_item(&item);
+        crate::commands::workspace::ensure_entry_allowed(item.original_path())?;
+        trash::os_limited::restore_all([item]).map_err(map_error)?;
+        Ok(restored)
+    }
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+)))]
+mod system {
+    use super::TrashedItem;
+    use crate::commands::error::{CommandError, CommandResult, ErrorKind};
+    use std::path::Path;
+    use std::time::SystemTime;
+
+    pub const SUPPORTED: bool = false;
+
+    pub fn list() -> CommandResult<Vec<TrashedItem>> {
+        Ok(Vec::new())
+    }
+
+    pub fn find_deleted(_path: &Path, _since: SystemTime) -> Option<TrashedItem> {
+        None
+    }
+
+    pub fn restore(_id: &str) -> CommandResult<TrashedItem> {
+        Err(CommandError::new(
+            ErrorKind::Unsupported,
+            "Restoring from the system trash is not supported on this platform",
+        ))
+    }
+}
+
+#[tauri::command]
+pub async fn remove_path(path: String) -> CommandResult<TrashedItem> {
+    tokio::task::spawn_blocking(move || {
+        let path = Path::new(&path);
+        let before = journal::fingerprint(path);
+        let item = trash_entry(path)?;
+        journal::record_delete(Path::new(&item.original_path), &item.id, before);
+        Ok(item)
+    })
+    .await
+    .map_err(|e| CommandError::other(format!("Delete failed: {}", e)))?
+}
+
+pub(crate) fn trash_entry(path: &Path) -> CommandResult<TrashedItem> {
+    fs::symlink_metadata(path).with_path(path)?;
+    let absolute = ensure_entry_allowed(path)?;
+    record_deletion(&absolute)?;
+
+    if !system::SUPPORTED {
+        return move_to_litcode_trash(&absolute);
+    }
+
+    let started = SystemTime::now();
+    match trash::delete(&absolute) {
+        Ok(()) => Ok(system::find_deleted(&absolute, started).unwrap_or_else(|| TrashedItem {
+            id: String::new(),
+            name: absolute
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            original_path: absolute.to_string_lossy().to_string(),
+            deleted_at: now_secs(),
+            location: "system".to_string(),
+        })),
+        Err(e) => move_to_litcode_trash(&absolute).map_err(|fallback| {
+            CommandError::other(format!("System trash failed ({}); litcode trash failed: {}", e, fallback))
+                .with_path(&absolute)
+        }),
+    }
+}
+
+#[tauri::command]
+pub async fn delete_path_permanently(path: String) -> CommandResult<()> {
+    tokio::task::spawn_blocking(move || {
+        let path = ensure_entry_allowed(&path)?;
+        record_deletion(&path)?;
+        remove_path_permanently(&path).with_path(&path)
+    })
+    .await
+    .map_err(|e| CommandError::other(format!("Delete failed: {}", e)))?
+}
+
+#[tauri::command]
+pub fn trash_list() -> CommandResult<Vec<TrashedItem>> {
+    let mut items = system::list().unwrap_or_default();
+    items.extend(list_litcode_trash()?);
+    items.sort_by_key(|item| std::cmp::Reverse(item.deleted_at));
+    Ok(items)
+}
+
+#[tauri::command]
+pub fn trash_restore(id: &str) -> CommandResult<TrashedItem> {
+    if id.starts_with(LITCODE_TRASH_PREFIX) && !id.contains(['/', '\\']) {
+        restore_from_litcode_trash(id)
+    } else {
+        system::restore(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::workspace::trust_root;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("litcode-trash-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        trust_root(&dir).unwrap()
+    }
+
+    #[test]
+    fn litcode_trash_round_trip() {
+        let root = scratch_dir("round-trip");
+        fs::create_dir_all(root.join("dir")).unwrap();
+        fs::write(root.join("dir/file.txt"), "data").unwrap();
+
+        let item = move_to_litcode_trash(&root.join("dir")).unwrap();
+        assert!(!root.join("dir").exists());
+        assert_eq!(item.location, "litcode");
+        assert!(list_litcode_trash().unwrap().iter().any(|i| i.id == item.id));
+
+        trash_restore(&item.id).unwrap();
+        assert_eq!(fs::read_to_string(root.join("dir/file.txt")).unwrap(), "data");
+        assert!(!list_litcode_trash().unwrap().iter().any(|i| i.id == item.id));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn litcode_trash_refuses_to_overwrite_on_restore() {
+        let root = scratch_dir("collision");
+        fs::write(root.join("file.txt"), "old").unwrap();
+        let item = move_to_litcode_trash(&root.join("file.txt")).unwrap();
+        fs::write(root.join("file.txt"), "new").unwrap();
+
+        let err = trash_restore(&item.id).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_to_string(root.join("file.txt")).unwrap(), "new");
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android")))]
+    #[test]
+    fn info_files_match_name_and_path() {
+        assert!(system::is_info_file_for("a.txt.trashinfo", "a.txt"));
+        assert!(system::is_info_file_for("a.txt.2.trashinfo", "a.txt"));
+        assert!(!system::is_info_file_for("a.txt.bak.trashinfo", "a.txt"));
+        assert!(!system::is_info_file_for("b.txt.trashinfo", "a.txt"));
+
+        let root = scratch_dir("info");
+        let info = root.join("a.txt.trashinfo");
+        fs::write(&info, "[Trash Info]\nPath=/ws/my%20dir/a.txt\nDeletionDate=2024-01-01T00:00:00\n").unwrap();
+        assert!(system::info_refers_to(&info, Path::new("/ws/my dir/a.txt")));
+        assert!(!system::info_refers_to(&info, Path::new("/ws/other/a.txt")));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,111 @@
+use super::error::{CommandError, CommandResult};
+use super::fs::{build_dir_entry, list_dir_page, DirEntry, GitListing, GitSnapshot, ReadDirOptions};
+use super::workspace::ensure_allowed;
+use rayon::prelude::*;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_TREE_DEPTH: usize = 1;
+const MAX_TREE_DEPTH: usize = 32;
+const DEFAULT_ENTRIES_PER_DIR: usize = 2000;
+
+struct TreeRequest {
+    depth: usize,
+    expanded: HashSet<PathBuf>,
+    options: ReadDirOptions,
+    max_entries: usize,
+    snapshot: Option<GitSnapshot>,
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    match (path.parent().map(Path::canonicalize), path.file_name()) {
+        (Some(Ok(parent)), Some(name)) => parent.join(name),
+        _ => path.canonicalize().unwrap_or_else(|_| path.to_path_buf()),
+    }
+}
+
+impl TreeRequest {
+    fn should_expand(&self, entry: &DirEntry, canonical: &Path, level: usize) -> bool {
+        if !entry.is_directory || level >= MAX_TREE_DEPTH {
+            return false;
+        }
+        if self.expanded.contains(canonical) {
+            return true;
+        }
+        !entry.is_symlink && !entry.is_ignored && level < self.depth
+    }
+
+    fn load(
+        &self,
+        entry: &mut DirEntry,
+        canonical: &Path,
+        level: usize,
+        git: Option<&GitListing>,
+        offset: usize,
+    ) -> CommandResult<()> {
+        let path = PathBuf::from(&entry.path);
+        let (mut children, remaining) =
+            list_dir_page(&path, canonical, git, &self.options, offset, self.max_entries)?;
+        if remaining > 0 {
+            entry.more_entries = Some(remaining);
+        }
+
+        children
+            .par_iter_mut()
+            .filter(|child| self.should_expand(child, &canonical.join(&child.name), level + 1))
+            .for_each_init(
+                || self.snapshot.as_ref().and_then(GitSnapshot::open),
+                |git, child| {
+                    let child_canonical = canonical.join(&child.name);
+                    if self.load(child, &child_canonical, level + 1, git.as_ref(), 0).is_err() {
+                        child.children = None;
+                    }
+                },
+            );
+
+        entry.children = Some(children);
+        Ok(())
+    }
+}
+
+fn canonical_root(path: &str) -> CommandResult<PathBuf> {
+    let root = ensure_allowed(path)?;
+    if !root.is_dir() {
+        return Err(CommandError::invalid_input("Tree root must be a directory").with_path(path));
+    }
+    Ok(root)
+}
+
+#[tauri::command]
+pub async fn read_tree(
+    path: String,
+    depth: Option<usize>,
+    expanded: Option<Vec<String>>,
+    options: Option<ReadDirOptions>,
+    max_entries: Option<usize>,
+    offset: Option<usize>,
+) -> CommandResult<DirEntry> {
+    tokio::task::spawn_blocking(move || {
+        let root = canonical_root(&path)?;
+        let snapshot = GitSnapshot::for_dir(&root);
+        let request = TreeRequest {
+            depth: depth.unwrap_or(DEFAULT_TREE_DEPTH).min(MAX_TREE_DEPTH),
+            expanded: expanded
+                .unwrap_or_default()
+                .iter()
+                .map(|p| normalize(Path::new(p)))
+                .collect(),
+            options: options.unwrap_or_default(),
+            max_entries: max_entries.unwrap_or(DEFAULT_ENTRIES_PER_DIR).max(1),
+            snapshot,
+        };
+
+        let git = request.snapshot.as_ref().and_then(GitSnapshot::open);
+        let mut entry = build_dir_entry(root.clone(), git.as_ref());
+        entry.path = path;
+        request.load(&mut entry, &root, 0, git.as_ref(), offset.unwrap_or(0))?;
+        Ok(entry)
+    })
+    .await
+    .map_err(|e| CommandError::other(format!("Failed to read tree: {}", e)))?
+}
@@ -0,0 +1,357 @@
+use super::error::{CommandError, CommandResult};
+use super::workspace::ensure_allowed;
+use git2::Repository;
+use notify_debouncer_full::notify::event::{ModifyKind, RenameMode};
+use notify_debouncer_full::notify::{EventKind, RecommendedWatcher, RecursiveMode};
+use notify_debouncer_full::{
+    new_debouncer, DebounceEventResult, DebouncedEvent, Debouncer, RecommendedCache,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FsChange {
+    pub kind: String,
+    pub path: String,
+    #[serde(rename = "oldPath")]
+    pub old_path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FsChangeBatch {
+    pub root: String,
+    pub changes: Vec<FsChange>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitStateChange {
+    pub root: String,
+    #[serde(rename = "repoPath")]
+    pub repo_path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WatchError {
+    pub root: String,
+    pub message: String,
+}
+
+type WatchDebouncer = Debouncer<RecommendedWatcher, RecommendedCache>;
+type WatcherMap = Arc<Mutex<HashMap<String, WatchDebouncer>>>;
+
+pub fn init_watch_state<R: tauri::Runtime>(app: &tauri::App<R>) {
+    app.manage(Arc::new(Mutex::new(HashMap::new())) as WatcherMap);
+}
+
+fn get_watcher_map(app: &AppHandle) -> WatcherMap {
+    app.state::<WatcherMap>().inner().clone()
+}
+
+struct WatchedRoot {
+    root: PathBuf,
+    workdir: Option<PathBuf>,
+    git_dir: Option<PathBuf>,
+}
+
+impl WatchedRoot {
+    fn new(root: PathBuf) -> Self {
+        let repo = Repository::discover(&root).ok();
+        let workdir = repo
+            .as_ref()
+            .and_then(|r| r.workdir())
+            .map(|p| p.canonicalize().unwrap_or_else(|_| p.to_path_buf()));
+        let git_dir = repo
+            .as_ref()
+            .map(|r| r.path().canonicalize().unwrap_or_else(|_| r.path().to_path_buf()));
+        WatchedRoot {
+            root,
+            workdir,
+            git_dir,
+        }
+    }
+
+    fn is_git_state_path(&self, path: &Path) -> bool {
+        let rel = match self.git_dir.as_ref().and_then(|g| path.strip_prefix(g).ok()) {
+            Some(rel) => rel,
+            None => return false,
+        };
+        rel == Path::new("HEAD") || rel == Path::new("index") || rel.starts_with("refs")
+    }
+
+    fn is_git_internal(&self, path: &Path) -> bool {
+        if let Some(git_dir) = &self.git_dir {
+            if path.starts_with(git_dir) {
+                return true;
+            }
+        }
+        path.components().any(|c| c.as_os_str() == ".git")
+    }
+
+    fn is_ignored(&self, repo: Option<&Repository>, path: &Path) -> bool {
+        let (repo, workdir) = match (repo, &self.workdir) {
+            (Some(r), Some(w)) => (r, w),
+            _ => return false,
+        };
+        match path.strip_prefix(workdir) {
+            Ok(rel) if !rel.as_os_str().is_empty() => repo.is_path_ignored(rel).unwrap_or(false),
+            _ => false,
+        }
+    }
+}
+
+fn collect_watch_dirs(watched: &WatchedRoot, repo: Option<&Repository>, dir: &Path, dirs: &mut Vec<PathBuf>) {
+    if watched.is_git_internal(dir) || watched.is_ignored(repo, dir) {
+        return;
+    }
+    dirs.push(dir.to_path_buf());
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            collect_watch_dirs(watched, repo, &entry.path(), dirs);
+        }
+    }
+}
+
+fn watch_tree(
+    debouncer: &mut WatchDebouncer,
+    watched: &WatchedRoot,
+    repo: Option<&Repository>,
+    dir: &Path,
+) -> CommandResult<()> {
+    let mut dirs = Vec::new();
+    collect_watch_dirs(watched, repo, dir, &mut dirs);
+    for dir in dirs {
+        debouncer
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .map_err(|e| CommandError::other(format!("Failed to watch: {}", e)).with_path(&dir))?;
+    }
+    Ok(())
+}
+
+fn watch_git_state(debouncer: &mut WatchDebouncer, git_dir: &Path) -> CommandResult<()> {
+    let watch_error = |e| CommandError::other(format!("Failed to watch: {}", e)).with_path(git_dir);
+    debouncer.watch(git_dir, RecursiveMode::NonRecursive).map_err(watch_error)?;
+    debouncer.watch(&git_dir.join("refs"), RecursiveMode::Recursive).map_err(watch_error)
+}
+
+fn emit_error(app: &AppHandle, root: &str, message: String) {
+    let _ = app.emit(
+        "fs:watch-error",
+        WatchError {
+            root: root.to_string(),
+            message,
+        },
+    );
+}
+
+fn change_from_event(event: &DebouncedEvent) -> Option<FsChange> {
+    let path_str = |p: &PathBuf| p.to_string_lossy().to_string();
+    let first = event.paths.first()?;
+
+    let (kind, path, old_path) = match event.kind {
+        EventKind::Create(_) => ("created", path_str(first), None),
+        EventKind::Remove(_) => ("removed", path_str(first), None),
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if event.paths.len() >= 2 => {
+            ("renamed", path_str(&event.paths[1]), Some(path_str(first)))
+        }
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => ("removed", path_str(first), None),
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => ("created", path_str(first), None),
+        EventKind::Modify(_) | EventKind::Any => ("modified", path_str(first), None),
+        EventKind::Access(_) | EventKind::Other => return None,
+    };
+
+    Some(FsChange {
+        kind: kind.to_string(),
+        path,
+        old_path,
+    })
+}
+
+fn handle_events(app: &AppHandle, watched: &WatchedRoot, events: Vec<DebouncedEvent>) {
+    let repo = watched
+        .workdir
+        .as_ref()
+        .and_then(|w| Repository::open(w).ok());
+
+    let mut changes: Vec<FsChange> = Vec::new();
+    let mut created_dirs: Vec<PathBuf> = Vec::new();
+    let mut git_changed = false;
+
+    for event in &events {
+        if event.paths.iter().any(|p| watched.is_git_state_path(p)) {
+            git_changed = true;
+        }
+
+        let change = match change_from_event(event) {
+            Some(c) => c,
+            None => continue,
+        };
+
+        let paths = std::iter::once(&change.path).chain(change.old_path.iter());
+        let visible = paths
+            .map(Path::new)
+            .any(|p| !watched.is_git_internal(p) && !watched.is_ignored(repo.as_ref(), p));
+        if !visible {
+            continue;
+        }
+        if change.kind != "removed" && change.kind != "modified" && Path::new(&change.path).is_dir() {
+            created_dirs.push(PathBuf::from(&change.path));
+        }
+
+        let duplicate = changes
+            .iter()
+            .any(|c| c.kind == change.kind && c.path == change.path && c.old_path == change.old_path);
+        if !duplicate {
+            changes.push(change);
+        }
+    }
+
+    let root = watched.root.to_string_lossy().to_string();
+
+    if !created_dirs.is_empty() {
+        let watcher_map = get_watcher_map(app);
+        let mut map = watcher_map.lock().unwrap();
+        if let Some(debouncer) = map.get_mut(&root) {
+            for dir in created_dirs {
+                if let Err(e) = watch_tree(debouncer, watched, repo.as_ref(), &dir) {
+                    emit_error(app, &root, e.to_string());
+                }
+            }
+        }
+    }
+
+    if !changes.is_empty() {
+        let _ = app.emit(
+            "fs:changed",
+            FsChangeBatch {
+                root: root.clone(),
+                changes,
+            },
+        );
+    }
+
+    if git_changed {
+        let repo_path = watched
+            .workdir
+            .as_ref()
+            .map(|w| w.to_string_lossy().to_string())
+            .unwrap_or_else(|| root.clone());
+        let _ = app.emit("git:changed", GitStateChange { root, repo_path });
+    }
+}
+
+#[tauri::command]
+pub fn watch_register(app: AppHandle, root: String) -> CommandResult<()> {
+    let root_path = ensure_allowed(&root)?;
+    if !root_path.is_dir() {
+        return Err(CommandError::invalid_input("Watch root must be a directory").with_path(&root));
+    }
+    let key = root_path.to_string_lossy().to_string();
+
+    let watcher_map = get_watcher_map(&app);
+    let mut map = watcher_map.lock().unwrap();
+    if map.contains_key(&key) {
+        return Ok(());
+    }
+
+    let watched = WatchedRoot::new(root_path.clone());
+    let repo = watched.workdir.as_ref().and_then(|w| Repository::open(w).ok());
+    let git_dir = watched.git_dir.clone();
+    let handler_app = app.clone();
+    let handler_watched = WatchedRoot::new(root_path.clone());
+    let handler_key = key.clone();
+    let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, None, move |result: DebounceEventResult| {
+        match result {
+            Ok(events) => handle_events(&handler_app, &handler_watched, events),
+            Err(errors) => {
+                for e in errors {
+                    emit_error(&handler_app, &handler_key, e.to_string());
+                }
+            }
+        }
+    })
+    .map_err(|e| {
+        CommandError::other(format!("Failed to start watcher: {}", e)).with_path(&root_path)
+    })?;
+
+    watch_tree(&mut debouncer, &watched, repo.as_ref(), &root_path)?;
+    if let Some(git_dir) = git_dir {
+        watch_git_state(&mut debouncer, &git_dir)?;
+    }
+
+    map.insert(key, debouncer);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn watch_unregister(app: AppHandle, root: String) -> CommandResult<()> {
+    let key = Path::new(&root)
+        .canonicalize()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or(root);
+
+    let watcher_map = get_watcher_map(&app);
+    let mut map = watcher_map.lock().unwrap();
+    if let Some(debouncer) = map.remove(&key) {
+        debouncer.stop_nonblocking();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::workspace::trust_root;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("litcode-watch-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        trust_root(&dir).unwrap()
+    }
+
+    #[test]
+    fn watch_dirs_skip_ignored_and_git_trees() {
+        let dir = scratch_dir("ignored");
+        Repository::init(&dir).unwrap();
+        fs::write(dir.join(".gitignore"), "target/\nnode_modules\n").unwrap();
+        for sub in ["src/nested", "target/debug/deps", "node_modules/pkg"] {
+            fs::create_dir_all(dir.join(sub)).unwrap();
+        }
+
+        let watched = WatchedRoot::new(dir.clone());
+        let repo = Repository::open(&dir).unwrap();
+        let mut dirs = Vec::new();
+        collect_watch_dirs(&watched, Some(&repo), &dir, &mut dirs);
+        dirs.sort();
+
+        assert_eq!(dirs, vec![dir.clone(), dir.join("src"), dir.join("src/nested")]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn watch_dirs_do_not_follow_symlinks() {
+        let dir = scratch_dir("symlink");
+        fs::create_dir_all(dir.join("real")).unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(dir.join("real"), dir.join("link")).unwrap();
+
+        let watched = WatchedRoot::new(dir.clone());
+        let mut dirs = Vec::new();
+        collect_watch_dirs(&watched, None, &dir, &mut dirs);
+        dirs.sort();
+
+        assert_eq!(dirs, vec![dir.clone(), dir.join("real")]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
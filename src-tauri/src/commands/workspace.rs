@@ -0,0 +1,257 @@
+use super::config::litcode_dir;
+use super::error::{CommandError, CommandResult, ErrorKind, ResultExt};
+use super::fs::atomic_write;
+use std::env;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+
+static TRUSTED_ROOTS: RwLock<Vec<PathBuf>> = RwLock::new(Vec::new());
+static GRANTS: Mutex<()> = Mutex::new(());
+
+pub fn init_workspace_state() {
+    if let Ok(initial) = env::var("LITCODE_INITIAL_PATH") {
+        if !initial.is_empty() {
+            let _ = trust_root(Path::new(&initial));
+        }
+    }
+    for root in load_grants() {
+        let _ = trust_root(&root);
+    }
+}
+
+fn grants_file() -> CommandResult<PathBuf> {
+    Ok(litcode_dir()?.join("workspaces.json"))
+}
+
+fn load_grants() -> Vec<PathBuf> {
+    grants_file()
+        .ok()
+        .and_then(|file| fs::read(file).ok())
+        .and_then(|json| serde_json::from_slice(&json).ok())
+        .unwrap_or_default()
+}
+
+fn save_grants(grants: &[PathBuf]) -> CommandResult<()> {
+    let file = grants_file()?;
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir).with_path(dir)?;
+    }
+    let json = serde_json::to_vec_pretty(grants).map_err(|e| CommandError::other(e.to_string()))?;
+    atomic_write(&file, &json)
+}
+
+fn grant_root(path: &Path) -> CommandResult<PathBuf> {
+    let _guard = GRANTS.lock().unwrap();
+    let root = trust_root(path)?;
+    let mut grants = load_grants();
+    if !grants.contains(&root) {
+        grants.push(root.clone());
+        save_grants(&grants)?;
+    }
+    Ok(root)
+}
+
+pub(crate) fn trust_root(path: &Path) -> CommandResult<PathBuf> {
+    let root = path.canonicalize().with_path(path)?;
+    let mut roots = TRUSTED_ROOTS.write().unwrap();
+    if !roots.contains(&root) {
+        roots.push(root.clone());
+    }
+    Ok(root)
+}
+
+const MAX_LINK_DEPTH: u32 = 40;
+
+fn resolve(path: &Path, follow_last: bool) -> CommandResult<PathBuf> {
+    resolve_links(path, follow_last, 0)
+}
+
+fn resolve_links(path: &Path, follow_last: bool, depth: u32) -> CommandResult<PathBuf> {
+    if depth > MAX_LINK_DEPTH {
+        return Err(CommandError::invalid_input("Too many levels of symbolic links").with_path(path));
+    }
+    if !path.is_absolute() {
+        return Err(CommandError::invalid_input("Path must be absolute").with_path(path));
+    }
+
+    let (parent, name) = match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) if !follow_last => (parent, Some(name)),
+        _ => (path, None),
+    };
+
+    let mut existing = parent;
+    let mut missing = Vec::new();
+    let base = loop {
+        match existing.canonicalize() {
+            Ok(resolved) => break resolved,
+            Err(_) if existing.is_symlink() => {
+                let link = fs::read_link(existing).with_path(path)?;
+                let target = existing.parent().map(|p| p.join(&link)).unwrap_or(link);
+                break resolve_links(&target, true, depth + 1)?;
+            }
+            Err(_) => match (existing.parent(), existing.file_name()) {
+                (Some(up), Some(component)) => {
+                    missing.push(component);
+                    existing = up;
+                }
+                _ => return Err(CommandError::not_found("Path does not exist").with_path(path)),
+            },
+        }
+    };
+
+    let mut resolved = base;
+    for component in missing.iter().rev().chain(name.iter()) {
+        if Path::new(component).components().any(|c| !matches!(c, Component::Normal(_))) {
+            return Err(CommandError::invalid_input("Path must be normalized").with_path(path));
+        }
+        resolved.push(component);
+    }
+    Ok(resolved)
+}
+
+fn check(path: &Path, follow_last: bool) -> CommandResult<PathBuf> {
+    let resolved = resolve(path, follow_last)?;
+    let roots = TRUSTED_ROOTS.read().unwrap();
+    if roots.iter().any(|root| resolved.starts_with(root)) {
+        Ok(resolved)
+    } else {
+        Err(CommandError::new(ErrorKind::PermissionDenied, "Path is outside the trusted workspace").with_path(path))
+    }
+}
+
+pub(crate) fn ensure_allowed(path: impl AsRef<Path>) -> CommandResult<PathBuf> {
+    check(path.as_ref(), true)
+}
+
+pub(crate) fn ensure_entry_allowed(path: impl AsRef<Path>) -> CommandResult<PathBuf> {
+    check(path.as_ref(), false)
+}
+
+#[tauri::command]
+pub async fn workspace_open(app: AppHandle) -> CommandResult<Option<String>> {
+    let picked = tokio::task::spawn_blocking(move || app.dialog().file().blocking_pick_folder())
+        .await
+        .map_err(|e| CommandError::other(format!("Folder dialog failed: {}", e)))?;
+    let path = match picked.and_then(|p| p.into_path().ok()) {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+    let root = grant_root(&path)?;
+    Ok(Some(root.to_string_lossy().to_string()))
+}
+
+#[tauri::command]
+pub fn workspace_untrust(path: &str) -> CommandResult<()> {
+    let _guard = GRANTS.lock().unwrap();
+    let root = Path::new(path).canonicalize().unwrap_or_else(|_| PathBuf::from(path));
+    TRUSTED_ROOTS.write().unwrap().retain(|r| *r != root);
+    let mut grants = load_grants();
+    if grants.contains(&root) {
+        grants.retain(|r| *r != root);
+        save_grants(&grants)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn workspace_roots() -> Vec<String> {
+    TRUSTED_ROOTS
+        .read()
+        .unwrap()
+        .iter()
+        .map(|r| r.to_string_lossy().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str, trusted: bool) -> PathBuf {
+        let dir = env::temp_dir().join(format!("litcode-workspace-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        if trusted {
+            trust_root(&dir).unwrap()
+        } else {
+            dir.canonicalize().unwrap()
+        }
+    }
+
+    fn is_denied(result: CommandResult<PathBuf>) -> bool {
+        matches!(result, Err(e) if e.kind == ErrorKind::PermissionDenied)
+    }
+
+    #[test]
+    fn allows_paths_under_trusted_root() {
+        let root = scratch_dir("inside", true);
+        fs::write(root.join("file.txt"), "x").unwrap();
+        assert_eq!(ensure_allowed(root.join("file.txt")).unwrap(), root.join("file.txt"));
+        assert_eq!(ensure_allowed(root.join("new/dir/file.txt")).unwrap(), root.join("new/dir/file.txt"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn rejects_untrusted_and_relative_paths() {
+        let outside = scratch_dir("untrusted", false);
+        assert!(is_denied(ensure_allowed(outside.join("file.txt"))));
+        assert!(ensure_allowed("relative/file.txt").is_err());
+        fs::remove_dir_all(&outside).unwrap();
+    }
+
+    #[test]
+    fn rejects_parent_components_in_missing_parts() {
+        let root = scratch_dir("parent", true);
+        assert!(ensure_allowed(root.join("missing/../../escape.txt")).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_symlink_leaving_trusted_root() {
+        let root = scratch_dir("link-out", true);
+        let outside = scratch_dir("link-target", false);
+        std::os::unix::fs::symlink(&outside, root.join("dir")).unwrap();
+        assert!(is_denied(ensure_allowed(root.join("dir/file.txt"))));
+        assert!(is_denied(ensure_entry_allowed(root.join("dir/file.txt"))));
+        fs::remove_dir_all(&root).unwrap();
+        fs::remove_dir_all(&outside).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_dangling_symlink_leaving_trusted_root() {
+        let root = scratch_dir("dangling-out", true);
+        let outside = scratch_dir("dangling-target", false);
+        std::os::unix::fs::symlink(outside.join("missing.txt"), root.join("link.txt")).unwrap();
+        assert!(is_denied(ensure_allowed(root.join("link.txt"))));
+        assert_eq!(ensure_entry_allowed(root.join("link.txt")).unwrap(), root.join("link.txt"));
+        fs::remove_dir_all(&root).unwrap();
+        fs::remove_dir_all(&outside).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn follows_dangling_symlink_inside_trusted_root() {
+        let root = scratch_dir("dangling-in", true);
+        std::os::unix::fs::symlink("missing.txt", root.join("link.txt")).unwrap();
+        assert_eq!(ensure_allowed(root.join("link.txt")).unwrap(), root.join("missing.txt"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn grants_persist_until_untrusted() {
+        let root = scratch_dir("grant", false);
+        assert_eq!(grant_root(&root).unwrap(), root);
+        assert!(load_grants().contains(&root));
+        assert!(ensure_allowed(root.join("file.txt")).is_ok());
+
+        workspace_untrust(root.to_str().unwrap()).unwrap();
+        assert!(!load_grants().contains(&root));
+        assert!(is_denied(ensure_allowed(root.join("file.txt"))));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -2,11 +2,25 @@ mod commands;
 
 use commands::{
     config_read, config_write,
-    create_dir, file_exists, git_diff, git_diff_untracked, git_revert_file, git_revert_hunk,
-    git_revert_lines, git_stage_file, git_status, git_unstage_file, git_show_file, git_show_staged_file,
-    read_dir, read_file, remove_path, rename_path, write_file,
+    create_dir, create_file, file_exists, file_info, read_file_lines, read_file_range, git_commit, git_diff, git_diff_untracked, git_revert_file, git_revert_hunk,
+    git_revert_lines, git_reflog, git_reflog_undo, git_reflog_undo_preview, git_stage_file, git_status, git_unstage_file, git_show_file, git_show_staged_file, git_log, git_verify_signatures, git_lfs_pointer,
+    git_checkpoint_create, git_checkpoint_list, git_checkpoint_diff, git_checkpoint_restore, git_checkpoint_delete,
+    load_file, read_dir, read_file, remove_path, rename_path, write_file, editorconfig_resolve,
     terminal_spawn, terminal_write, terminal_resize, terminal_kill, init_terminal_state,
     search_content, search_files,
+    delete_path_permanently, trash_list, trash_restore,
+    copy_paths, move_paths, duplicate_path, read_tree,
+    archive_list, archive_read_entry, archive_extract,
+    disk_usage, disk_usage_cancel,
+    diff_texts, diff_paths, diff_path_with_text, diff_folders,
+    history_list, history_diff, history_restore, history_deleted,
+    backup_buffer, backup_list, backup_prune, backup_restore, backup_discard,
+    template_list, template_apply,
+    fs_undo, fs_redo, fs_journal,
+    get_file_permissions, set_file_permissions,
+    rename_references_preview, rename_with_references,
+    watch_register, watch_unregister, init_watch_state,
+    workspace_open, workspace_untrust, workspace_roots, init_workspace_state,
 };
 use std::env;
 
@@ -105,30 +119,85 @@ fn is_cli_installed() -> bool {
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_dialog::init())
         .setup(|app| {
             init_terminal_state(app);
+            init_watch_state(app);
+            init_workspace_state();
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             config_read,
             config_write,
             read_file,
+            load_file,
+            file_info,
+            read_file_range,
+            read_file_lines,
             write_file,
+            editorconfig_resolve,
             read_dir,
+            read_tree,
+            archive_list,
+            archive_read_entry,
+            archive_extract,
+            disk_usage,
+            disk_usage_cancel,
+            diff_texts,
+            diff_paths,
+            diff_path_with_text,
+            diff_folders,
             file_exists,
             create_dir,
+            create_file,
             remove_path,
+            delete_path_permanently,
+            trash_list,
+            trash_restore,
+            copy_paths,
+            move_paths,
+            duplicate_path,
+            history_list,
+            history_diff,
+            history_restore,
+            history_deleted,
+            backup_buffer,
+            backup_list,
+            backup_prune,
+            backup_restore,
+            backup_discard,
             rename_path,
+            fs_undo,
+            fs_redo,
+            fs_journal,
+            get_file_permissions,
+            set_file_permissions,
+            rename_references_preview,
+            rename_with_references,
+            template_list,
+            template_apply,
             git_status,
             git_diff,
             git_diff_untracked,
             git_revert_file,
             git_revert_hunk,
             git_revert_lines,
+            git_reflog,
+            git_reflog_undo_preview,
+            git_reflog_undo,
             git_stage_file,
             git_unstage_file,
             git_show_file,
             git_show_staged_file,
+            git_commit,
+            git_log,
+            git_verify_signatures,
+            git_lfs_pointer,
+            git_checkpoint_create,
+            git_checkpoint_list,
+            git_checkpoint_diff,
+            git_checkpoint_restore,
+            git_checkpoint_delete,
             get_initial_path,
             install_cli,
             uninstall_cli,
@@ -139,6 +208,11 @@ pub fn run() {
             terminal_kill,
             search_content,
             search_files,
+            watch_register,
+            watch_unregister,
+            workspace_open,
+            workspace_untrust,
+            workspace_roots,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
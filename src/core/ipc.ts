@@ -1,7 +1,7 @@
 // IPC wrapper for Tauri commands
 
 import { invoke } from '@tauri-apps/api/core';
-import type { DirEntry } from './types';
+import type { DirEntry, FileVersion, LoadedFile } from './types';
 
 // File system commands
 export const fs = {
@@ -13,6 +13,14 @@ export const fs = {
     await invoke('write_file', { path, content });
   },
 
+  async loadFile(path: string): Promise<LoadedFile> {
+    return invoke<LoadedFile>('load_file', { path });
+  },
+
+  async writeFileChecked(path: string, content: string, expectedVersion: FileVersion): Promise<FileVersion> {
+    return invoke<FileVersion>('write_file', { path, content, expectedVersion });
+  },
+
   async readDir(path: string): Promise<DirEntry[]> {
     return invoke<DirEntry[]>('read_dir', { path });
   },
@@ -44,7 +52,7 @@ export interface GitStatus {
 
 export interface GitFileStatus {
   path: string;
-  status: 'modified' | 'added' | 'deleted' | 'renamed' | 'untracked';
+  status: 'modified' | 'modeChanged' | 'added' | 'deleted' | 'renamed' | 'untracked';
   staged: boolean;
 }
 
@@ -107,6 +115,51 @@ export const git = {
   },
 };
 
+// Compare commands
+export interface CompareOptions {
+  ignoreWhitespace?: boolean;
+  contextLines?: number;
+  applySkipRules?: boolean;
+}
+
+export interface FolderDiffEntry {
+  path: string;
+  status: 'added' | 'removed' | 'changed';
+  isDirectory: boolean;
+  oldSize: number | null;
+  newSize: number | null;
+}
+
+export interface FolderDiff {
+  oldPath: string;
+  newPath: string;
+  entries: FolderDiffEntry[];
+  truncated: boolean;
+}
+
+export const compare = {
+  async diffTexts(
+    oldText: string,
+    newText: string,
+    label?: string,
+    options?: CompareOptions
+  ): Promise<GitDiff> {
+    return invoke<GitDiff>('diff_texts', { oldText, newText, label, options });
+  },
+
+  async diffPaths(oldPath: string, newPath: string, options?: CompareOptions): Promise<GitDiff> {
+    return invoke<GitDiff>('diff_paths', { oldPath, newPath, options });
+  },
+
+  async diffPathWithText(path: string, text: string, options?: CompareOptions): Promise<GitDiff> {
+    return invoke<GitDiff>('diff_path_with_text', { path, text, options });
+  },
+
+  async diffFolders(oldPath: string, newPath: string, options?: CompareOptions): Promise<FolderDiff> {
+    return invoke<FolderDiff>('diff_folders', { oldPath, newPath, options });
+  },
+};
+
 // Debug commands (DAP)
 export interface DebugConfig {
   type: 'node' | 'python' | 'go';
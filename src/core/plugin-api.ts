@@ -8,6 +8,8 @@ import type {
   StatusBarItem,
   Command,
   LanguageConfig,
+  FileVersion,
+  LoadedFile,
 } from './types';
 
 export interface PluginStorage {
@@ -56,6 +58,8 @@ export interface PluginAPI {
   fs: {
     readFile(path: string): Promise<string>;
     writeFile(path: string, content: string): Promise<void>;
+    loadFile(path: string): Promise<LoadedFile>;
+    writeFileChecked(path: string, content: string, expectedVersion: FileVersion): Promise<FileVersion>;
     readDir(path: string): Promise<DirEntry[]>;
     exists(path: string): Promise<boolean>;
     mkdir(path: string): Promise<void>;
@@ -285,6 +289,9 @@ export function createPluginAPI(pluginId: string): PluginAPI {
     fs: {
       readFile: (path) => invoke<string>('read_file', { path }),
       writeFile: (path, content) => invoke('write_file', { path, content }),
+      loadFile: (path) => invoke<LoadedFile>('load_file', { path }),
+      writeFileChecked: (path, content, expectedVersion) =>
+        invoke<FileVersion>('write_file', { path, content, expectedVersion }),
       readDir: (path) => invoke<DirEntry[]>('read_dir', { path }),
       exists: (path) => invoke<boolean>('file_exists', { path }),
       mkdir: (path) => invoke('create_dir', { path }),
@@ -12,7 +12,215 @@ export interface DirEntry {
   path: string;
   name: string;
   isDirectory: boolean;
-  children?: DirEntry[];
+  size?: number;
+  mtime?: number;
+  mode?: number | null;
+  uid?: number | null;
+  gid?: number | null;
+  readonly?: boolean;
+  isSymlink?: boolean;
+  symlinkTarget?: string | null;
+  isExecutable?: boolean;
+  isHidden?: boolean;
+  isIgnored?: boolean;
+  gitStatus?: string | null;
+  children?: DirEntry[] | null;
+  moreEntries?: number | null;
+}
+
+export interface ReadDirOptions {
+  showHidden?: boolean;
+  hideIgnored?: boolean;
+}
+
+export type CommandErrorKind =
+  | 'NotFound'
+  | 'PermissionDenied'
+  | 'AlreadyExists'
+  | 'Conflict'
+  | 'NotARepository'
+  | 'InvalidRegex'
+  | 'InvalidInput'
+  | 'Unsupported'
+  | 'Io'
+  | 'Git'
+  | 'Other';
+
+export interface CommandError {
+  kind: CommandErrorKind;
+  message: string;
+  path: string | null;
+  code: number | null;
+}
+
+export interface FileVersion {
+  mtime: number;
+  size: number;
+  hash: string;
+}
+
+export interface LoadedFile {
+  content: string;
+  version: FileVersion;
+  encoding: string;
+  bom: boolean;
+  hadErrors: boolean;
+}
+
+export interface EditorConfig {
+  indentStyle: 'space' | 'tab' | null;
+  indentSize: number | null;
+  tabWidth: number | null;
+  endOfLine: 'lf' | 'crlf' | 'cr' | null;
+  charset: string | null;
+  trimTrailingWhitespace: boolean | null;
+  insertFinalNewline: boolean | null;
+  properties: Record<string, string>;
+}
+
+export interface TemplateVariable {
+  name: string;
+  prompt: string | null;
+  default: string | null;
+}
+
+export interface TemplateInfo {
+  id: string;
+  name: string;
+  description: string | null;
+  source: 'user' | 'workspace';
+  variables: TemplateVariable[];
+  error: string | null;
+}
+
+export interface ReferenceEdit {
+  start: number;
+  end: number;
+  line: number;
+  column: number;
+  oldText: string;
+  newText: string;
+  lineContent: string;
+}
+
+export interface FileReferenceEdits {
+  path: string;
+  version: FileVersion;
+  edits: ReferenceEdit[];
+}
+
+export interface RenamePreview {
+  oldPath: string;
+  newPath: string;
+  source: 'languageServer' | 'textSearch';
+  files: FileReferenceEdits[];
+  truncated: boolean;
+}
+
+export interface FilePermissions {
+  path: string;
+  mode: number | null;
+  modeString: string | null;
+  uid: number | null;
+  gid: number | null;
+  readonly: boolean;
+  isExecutable: boolean;
+}
+
+export interface PermissionsResult {
+  permissions: FilePermissions;
+  changed: number;
+}
+
+export interface TransferEntry {
+  source: string;
+  destination: string;
+  status: string;
+}
+
+export interface TransferResult {
+  operationId: string;
+  entries: TransferEntry[];
+}
+
+export interface ArchiveEntry {
+  path: string;
+  isDirectory: boolean;
+  isSymlink: boolean;
+  size: number;
+  compressedSize: number | null;
+  mtime: number | null;
+  mode: number | null;
+}
+
+export interface ArchiveListing {
+  path: string;
+  format: 'zip' | 'tar' | 'tar.gz';
+  entries: ArchiveEntry[];
+  truncated: boolean;
+}
+
+export interface ArchiveEntryContent {
+  path: string;
+  size: number;
+  isBinary: boolean;
+  encoding: string | null;
+  text: string | null;
+  bytes: number[] | null;
+  truncated: boolean;
+}
+
+export interface DiskUsageOptions {
+  applySkipRules?: boolean;
+  maxChildren?: number | null;
+}
+
+export interface DiskUsageEntry {
+  path: string;
+  name: string;
+  isDirectory: boolean;
+  size: number;
+  fileCount: number;
+}
+
+export interface DiskUsage {
+  operationId: string;
+  path: string;
+  size: number;
+  fileCount: number;
+  dirCount: number;
+  children: DiskUsageEntry[];
+  moreChildren: number;
+  errors: number;
+  cancelled: boolean;
+}
+
+export interface DiskUsageProgress {
+  operationId: string;
+  currentPath: string | null;
+  size: number;
+  fileCount: number;
+  dirCount: number;
+  done: boolean;
+}
+
+export interface BufferBackup {
+  bufferId: string;
+  path: string | null;
+  savedAt: number;
+  size: number;
+  baseVersion: FileVersion | null;
+}
+
+export interface RecoverableBuffer {
+  backup: BufferBackup;
+  diskState: 'unchanged' | 'changed' | 'deleted' | 'untitled';
+  diskVersion: FileVersion | null;
+}
+
+export interface RestoredBuffer {
+  backup: BufferBackup;
+  content: string;
 }
 
 export interface SidebarConfig {
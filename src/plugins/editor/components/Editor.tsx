@@ -5,21 +5,87 @@ import { defaultKeymap, history, historyKeymap } from '@codemirror/commands';
 import { oneDark } from '@codemirror/theme-one-dark';
 import { invoke } from '@tauri-apps/api/core';
 import { eventBus, Events, pluginRegistry } from '../../../core';
+import type { FileVersion, LoadedFile, RecoverableBuffer, RestoredBuffer } from '../../../core/types';
 import { baseEditorTheme as baseTheme, getLanguageExtension } from '../../../lib/editor-utils';
 
+const BACKUP_DELAY_MS = 1000;
+
 export function Editor() {
   const containerRef = useRef<HTMLDivElement>(null);
   const viewRef = useRef<EditorView | null>(null);
   const [currentPath, setCurrentPath] = useState<string | null>(null);
   const currentTabIdRef = useRef<string | null>(null);
   const originalContentRef = useRef<string>('');
+  const versionRef = useRef<FileVersion | null>(null);
+  const encodingRef = useRef<{ encoding: string; bom: boolean; hadErrors: boolean } | null>(null);
   const loadRequestIdRef = useRef<number>(0);
+  const pathRef = useRef<string | null>(null);
+  const workspaceRef = useRef<string>('');
+  const backupTimersRef = useRef(new Map<string, ReturnType<typeof setTimeout>>());
+  const restoredRef = useRef(new Map<string, string>());
+
+  const scheduleBackup = useCallback((content: string, isDirty: boolean, path = pathRef.current) => {
+    const workspace = workspaceRef.current;
+    const baseVersion = versionRef.current;
+    if (!path || !workspace) return;
+    clearTimeout(backupTimersRef.current.get(path));
+
+    backupTimersRef.current.set(path, setTimeout(() => {
+      backupTimersRef.current.delete(path);
+      const request = isDirty
+        ? invoke('backup_buffer', { workspace, bufferId: path, path, content, baseVersion })
+        : invoke('backup_discard', { workspace, bufferId: path });
+      request.catch((err) => console.error('Failed to back up buffer:', err));
+    }, isDirty ? BACKUP_DELAY_MS : 0));
+  }, []);
+
+  const recoverBackups = useCallback(async (workspace: string) => {
+    await invoke<number>('backup_prune', { workspace });
+    const buffers = (await invoke<RecoverableBuffer[]>('backup_list', { workspace }))
+      .filter((buffer) => buffer.backup.path);
+    if (buffers.length === 0) return;
+
+    const names = buffers.map(({ backup, diskState }) => {
+      const name = backup.path!.split('/').pop();
+      if (diskState === 'changed') return `${name} (changed on disk since)`;
+      if (diskState === 'deleted') return `${name} (deleted on disk, will be recreated)`;
+      return name;
+    });
+    const recover = window.confirm(
+      `Recover unsaved changes from the last session?\n\n${names.join('\n')}`
+    );
+
+    for (const [index, { backup, diskState }] of buffers.entries()) {
+      const path = backup.path!;
+      if (!recover) {
+        await invoke('backup_discard', { workspace, bufferId: backup.bufferId });
+        continue;
+      }
+      const { content } = await invoke<RestoredBuffer>('backup_restore', { workspace, bufferId: backup.bufferId });
+      if (diskState === 'deleted') {
+        try {
+          await invoke<FileVersion>('write_file', { path, content, expectedVersion: null });
+        } catch (err) {
+          console.error(`Failed to recreate ${path}:`, err);
+          continue;
+        }
+      } else {
+        restoredRef.current.set(path, content);
+      }
+      const id = `tab-${Date.now()}-${index}`;
+      const name = path.split('/').pop() || path;
+      eventBus.emit(Events.TAB_OPEN, { id, path, name });
+      eventBus.emit(Events.FILE_OPEN, { id, path, name });
+    }
+  }, []);
 
   const createUpdateListener = useCallback((tabId: string) => {
     return EditorView.updateListener.of((update) => {
       if (update.docChanged) {
-        const isDirty = update.state.doc.toString() !== originalContentRef.current;
+        const content = update.state.doc.toString();
+        const isDirty = content !== originalContentRef.current;
         eventBus.emit(Events.TAB_DIRTY, { id: tabId, isDirty });
+        scheduleBackup(content, isDirty);
       }
 
       if (update.selectionSet) {
@@ -34,7 +100,7 @@ export function Editor() {
         });
       }
     });
-  }, []);
+  }, [scheduleBackup]);
 
   const createEditorState = useCallback((content: string, filename: string, tabId: string) => {
     return EditorState.create({
@@ -76,28 +142,54 @@ export function Editor() {
 
     viewRef.current = view;
 
+    invoke<string>('get_initial_path')
+      .then((path) => {
+        workspaceRef.current = path;
+        if (path) return recoverBackups(path);
+      })
+      .catch((err) => console.error('Failed to recover backups:', err));
+
     return () => {
+      backupTimersRef.current.forEach(clearTimeout);
       view.destroy();
     };
-  }, []);
+  }, [recoverBackups]);
 
   useEffect(() => {
+    const takeRestored = (path: string) => {
+      const content = restoredRef.current.get(path);
+      restoredRef.current.delete(path);
+      return content;
+    };
+
+    const markRestoredDirty = (id: string, diskContent: string) => {
+      const content = viewRef.current?.state.doc.toString();
+      if (content !== undefined && content !== diskContent) {
+        eventBus.emit(Events.TAB_DIRTY, { id, isDirty: true });
+        scheduleBackup(content, true);
+      }
+    };
+
     const handleFileOpen = async (data: unknown) => {
       const { id, path, name } = data as { id: string; path: string; name: string };
       currentTabIdRef.current = id;
       const requestId = ++loadRequestIdRef.current;
 
       try {
-        const content = await invoke<string>('read_file', { path });
+        const { content, version, encoding, bom, hadErrors } = await invoke<LoadedFile>('load_file', { path });
         
         if (requestId !== loadRequestIdRef.current) return;
         
         originalContentRef.current = content;
+        versionRef.current = version;
+        encodingRef.current = { encoding, bom, hadErrors };
+        pathRef.current = path;
         setCurrentPath(path);
 
         if (viewRef.current) {
-          viewRef.current.setState(createEditorState(content, name, id));
+          viewRef.current.setState(createEditorState(takeRestored(path) ?? content, name, id));
         }
+        markRestoredDirty(id, content);
 
         pluginRegistry.setCurrentFile({ path, name, isDirectory: false });
       } catch (err) {
@@ -111,17 +203,21 @@ export function Editor() {
       const requestId = ++loadRequestIdRef.current;
 
       try {
-        const content = await invoke<string>('read_file', { path });
+        const { content, version, encoding, bom, hadErrors } = await invoke<LoadedFile>('load_file', { path });
         
         if (requestId !== loadRequestIdRef.current) return;
         
         originalContentRef.current = content;
+        versionRef.current = version;
+        encodingRef.current = { encoding, bom, hadErrors };
+        pathRef.current = path;
         setCurrentPath(path);
 
         if (viewRef.current) {
           const name = path.split('/').pop() || '';
-          viewRef.current.setState(createEditorState(content, name, id));
+          viewRef.current.setState(createEditorState(takeRestored(path) ?? content, name, id));
         }
+        markRestoredDirty(id, content);
       } catch (err) {
         console.error('Failed to load file:', err);
       }
@@ -133,9 +229,27 @@ export function Editor() {
       if (!path || !viewRef.current) return;
 
       const content = viewRef.current.state.doc.toString();
+      const lossy = encodingRef.current?.hadErrors ?? false;
+      if (lossy && !window.confirm(
+        `${path} contains bytes that are not valid ${encodingRef.current?.encoding}. ` +
+        'Saving will replace them permanently. Save anyway?'
+      )) {
+        return;
+      }
       try {
-        await invoke('write_file', { path, content });
+        versionRef.current = await invoke<FileVersion>('write_file', {
+          path,
+          content,
+          expectedVersion: versionRef.current,
+          encoding: encodingRef.current?.encoding,
+          bom: encodingRef.current?.bom,
+          allowLossy: lossy,
+        });
+        if (encodingRef.current) {
+          encodingRef.current.hadErrors = false;
+        }
         originalContentRef.current = content;
+        scheduleBackup(content, false);
         if (tabId) {
           eventBus.emit(Events.TAB_DIRTY, { id: tabId, isDirty: false });
         }
@@ -155,8 +269,12 @@ export function Editor() {
     const handleTabClose = (data: unknown) => {
       const { id } = data as { id: string };
       if (currentTabIdRef.current === id) {
+        scheduleBackup('', false);
         setCurrentPath(null);
         originalContentRef.current = '';
+        versionRef.current = null;
+        encodingRef.current = null;
+        pathRef.current = null;
         currentTabIdRef.current = null;
         if (viewRef.current) {
           viewRef.current.setState(EditorState.create({
@@ -189,7 +307,7 @@ export function Editor() {
       unsubSave();
       window.removeEventListener('keydown', handleKeyDown);
     };
-  }, [currentPath, createEditorState]);
+  }, [currentPath, createEditorState, scheduleBackup]);
 
   return (
     <div className="h-full w-full relative">
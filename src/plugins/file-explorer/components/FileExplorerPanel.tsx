@@ -1,14 +1,17 @@
 import { useState, useEffect, useCallback, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { IconButton } from '../../../components';
 import { eventBus, Events, type DirEntry } from '../../../core';
 import { FileTree, type FileTreeRef } from './FileTree';
+import { errorMessage } from '../../../lib/utils';
 
 export function FileExplorerPanel() {
   const [rootPath, setRootPath] = useState<string>('');
   const [entries, setEntries] = useState<DirEntry[]>([]);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [watchError, setWatchError] = useState<string | null>(null);
   const fileTreeRef = useRef<FileTreeRef>(null);
 
   const loadDirectory = useCallback(async (path: string) => {
@@ -20,7 +23,7 @@ export function FileExplorerPanel() {
       setEntries(result);
       setTimeout(() => eventBus.emit('root-path:change', { path }), 0);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
       setEntries([]);
     } finally {
       setLoading(false);
@@ -48,6 +51,22 @@ export function FileExplorerPanel() {
     };
   }, [rootPath, loadDirectory]);
 
+  useEffect(() => {
+    if (!rootPath) return;
+    setWatchError(null);
+    invoke('watch_register', { root: rootPath }).catch((err) => setWatchError(errorMessage(err)));
+    const unlistenChanged = listen('fs:changed', () => loadDirectory(rootPath));
+    const unlistenError = listen<{ root: string; message: string }>('fs:watch-error', (event) => {
+      setWatchError(event.payload.message);
+    });
+
+    return () => {
+      unlistenChanged.then((unlisten) => unlisten());
+      unlistenError.then((unlisten) => unlisten());
+      invoke('watch_unregister', { root: rootPath }).catch(() => {});
+    };
+  }, [rootPath, loadDirectory]);
+
   const handleFileClick = (entry: DirEntry) => {
     if (!entry.isDirectory) {
       const id = `tab-${Date.now()}`;
@@ -60,6 +79,18 @@ export function FileExplorerPanel() {
     }
   };
 
+  const handleOpenFolder = async () => {
+    try {
+      const path = await invoke<string | null>('workspace_open');
+      if (path) {
+        setRootPath(path);
+        loadDirectory(path);
+      }
+    } catch (err) {
+      setError(errorMessage(err));
+    }
+  };
+
   const handleNewFile = () => {
     if (rootPath) {
       fileTreeRef.current?.triggerNewFile(rootPath);
@@ -79,6 +110,12 @@ export function FileExplorerPanel() {
       <div className="flex justify-between items-center px-3 py-2 min-h-header border-b border-default">
         <span className="text-xs font-semibold uppercase tracking-wide text-fg-secondary">EXPLORER</span>
         <div className="flex gap-1">
+          <IconButton
+            icon="folder-open"
+            size="sm"
+            onClick={handleOpenFolder}
+            title="Open Folder"
+          />
           <IconButton
             icon="file-plus"
             size="sm"
@@ -107,6 +144,11 @@ export function FileExplorerPanel() {
           <span className="text-xs font-semibold text-fg-primary truncate block" title={rootPath}>
             {folderName}
           </span>
+          {watchError && (
+            <span className="text-xs text-fg-secondary truncate block" title={watchError}>
+              Live updates unavailable: {watchError}
+            </span>
+          )}
         </div>
       )}
 
@@ -2,9 +2,10 @@ import { useState, useEffect, useCallback, forwardRef, useImperativeHandle } fro
 import { invoke } from '@tauri-apps/api/core';
 import { createPortal } from 'react-dom';
 import { ContextMenu, type ContextMenuItem } from '../../../components';
-import { eventBus, type DirEntry } from '../../../core';
+import { eventBus, type DirEntry, type DiskUsage, type TransferResult } from '../../../core';
 import { InlineInput, type InlineInputType } from './InlineInput';
 import { getFileIcon } from './file-icons';
+import { errorMessage } from '../../../lib/utils';
 
 interface FileTreeProps {
   entries: DirEntry[];
@@ -83,11 +84,54 @@ export const FileTree = forwardRef<FileTreeRef, FileTreeProps>(function FileTree
         await invoke('remove_path', { path: entry.path });
         eventBus.emit('file-explorer:refresh', {});
       } catch (err) {
-        alert(`Failed to delete: ${err}`);
+        alert(`Failed to delete: ${errorMessage(err)}`);
       }
     }
   }, [closeContextMenu]);
 
+  const handleToggleExecutable = useCallback(async (entry: DirEntry) => {
+    closeContextMenu();
+    try {
+      await invoke('set_file_permissions', {
+        path: entry.path,
+        mode: entry.isExecutable ? 'a-x' : '+x',
+      });
+      eventBus.emit('file-explorer:refresh', {});
+    } catch (err) {
+      alert(`Failed to change permissions: ${errorMessage(err)}`);
+    }
+  }, [closeContextMenu]);
+
+  const handleExtract = useCallback(async (entry: DirEntry) => {
+    closeContextMenu();
+    const parentPath = entry.path.substring(0, entry.path.lastIndexOf('/'));
+    try {
+      const result = await invoke<TransferResult>('archive_extract', {
+        path: entry.path,
+        destinationDir: parentPath,
+        policy: 'rename',
+      });
+      const blocked = result.entries.filter((e) => e.status === 'blocked');
+      if (blocked.length > 0) {
+        alert(`Skipped ${blocked.length} entries with unsafe paths`);
+      }
+      eventBus.emit('file-explorer:refresh', {});
+    } catch (err) {
+      alert(`Failed to extract: ${errorMessage(err)}`);
+    }
+  }, [closeContextMenu]);
+
+  const handleDiskUsage = useCallback(async (entry: DirEntry) => {
+    closeContextMenu();
+    try {
+      const usage = await invoke<DiskUsage>('disk_usage', { path: entry.path });
+      const lines = usage.children.map((child) => `${formatSize(child.size)}  ${child.name}`);
+      alert(`${entry.name}: ${formatSize(usage.size)} in ${usage.fileCount} files\n\n${lines.join('\n')}`);
+    } catch (err) {
+      alert(`Failed to analyze disk usage: ${errorMessage(err)}`);
+    }
+  }, [closeContextMenu]);
+
   const handleInlineInputSubmit = useCallback(async (value: string) => {
     if (!inlineInput || !value.trim()) {
       setInlineInput(null);
@@ -99,7 +143,7 @@ export const FileTree = forwardRef<FileTreeRef, FileTreeProps>(function FileTree
 
     try {
       if (inlineInput.type === 'new-file') {
-        await invoke('write_file', { path: newPath, content: '' });
+        await invoke('create_file', { path: newPath });
       } else if (inlineInput.type === 'new-folder') {
         await invoke('create_dir', { path: newPath });
       } else if (inlineInput.type === 'rename' && inlineInput.targetEntry) {
@@ -110,7 +154,7 @@ export const FileTree = forwardRef<FileTreeRef, FileTreeProps>(function FileTree
       }
       eventBus.emit('file-explorer:refresh', {});
     } catch (err) {
-      alert(`Operation failed: ${err}`);
+      alert(`Operation failed: ${errorMessage(err)}`);
     }
 
     setInlineInput(null);
@@ -143,10 +187,40 @@ export const FileTree = forwardRef<FileTreeRef, FileTreeProps>(function FileTree
             closeContextMenu();
           }
         },
+        {
+          id: 'disk-usage',
+          label: 'Analyze Disk Usage',
+          icon: 'search',
+          onClick: () => handleDiskUsage(entry)
+        },
         { id: 'sep1', label: '', separator: true }
       );
     }
 
+    if (!entry.isDirectory && isArchive(entry.name)) {
+      items.push(
+        {
+          id: 'extract-here',
+          label: 'Extract Here',
+          icon: 'arrow-down',
+          onClick: () => handleExtract(entry)
+        },
+        { id: 'sep-archive', label: '', separator: true }
+      );
+    }
+
+    if (!entry.isDirectory && entry.mode != null) {
+      items.push(
+        {
+          id: 'toggle-executable',
+          label: entry.isExecutable ? 'Remove Executable Permission' : 'Make Executable',
+          icon: 'terminal',
+          onClick: () => handleToggleExecutable(entry)
+        },
+        { id: 'sep2', label: '', separator: true }
+      );
+    }
+
     items.push(
       { 
         id: 'rename', 
@@ -167,7 +241,7 @@ export const FileTree = forwardRef<FileTreeRef, FileTreeProps>(function FileTree
     );
 
     return items;
-  }, [showInlineInput, closeContextMenu, handleDelete]);
+  }, [showInlineInput, closeContextMenu, handleDelete, handleToggleExecutable, handleExtract, handleDiskUsage]);
 
   const shouldShowInlineInput = inlineInput && 
     inlineInput.parentPath === currentPath && 
@@ -227,6 +301,43 @@ interface FileTreeItemProps {
   onInlineInputCancel: () => void;
 }
 
+function formatSize(bytes: number): string {
+  const units = ['B', 'KB', 'MB', 'GB', 'TB'];
+  let value = bytes;
+  let unit = 0;
+  while (value >= 1024 && unit < units.length - 1) {
+    value /= 1024;
+    unit++;
+  }
+  return `${unit === 0 ? value : value.toFixed(1)} ${units[unit]}`;
+}
+
+function isArchive(name: string): boolean {
+  return /\.(zip|tar|tar\.gz|tgz)$/i.test(name);
+}
+
+function formatMode(mode: number, isDirectory: boolean): string {
+  const bits = ['r', 'w', 'x'];
+  let result = isDirectory ? 'd' : '-';
+  for (let shift = 6; shift >= 0; shift -= 3) {
+    for (let i = 0; i < 3; i++) {
+      result += mode & (1 << (shift + 2 - i)) ? bits[i] : '-';
+    }
+  }
+  return `${result} (${(mode & 0o7777).toString(8).padStart(4, '0')})`;
+}
+
+function formatEntryMetadata(entry: DirEntry): string {
+  const lines = [entry.path];
+  if (entry.mode != null) {
+    lines.push(`Mode: ${formatMode(entry.mode, entry.isDirectory)}`);
+  }
+  if (entry.uid != null && entry.gid != null) {
+    lines.push(`Owner: ${entry.uid}:${entry.gid}`);
+  }
+  return lines.join('\n');
+}
+
 function FileTreeItem({
   entry,
   onFileClick,
@@ -303,6 +414,7 @@ function FileTreeItem({
         style={{ paddingLeft: `${level * 16 + 8}px` }}
         onClick={handleClick}
         onContextMenu={(e) => onContextMenu(e, entry)}
+        title={formatEntryMetadata(entry)}
       >
         <span className="flex items-center justify-center shrink-0 w-4 h-4">
           {getFileIcon(entry.name, entry.isDirectory, expanded)}
@@ -1,7 +1,7 @@
 import { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { IconButton, Button } from '../../../components';
-import { cn } from '../../../lib/utils';
+import { cn, errorMessage } from '../../../lib/utils';
 import { type DiffViewMode } from '../../../core';
 
 interface GitDiffLine {
@@ -58,7 +58,7 @@ export function DiffViewer({ repoPath, filePath, staged, isUntracked, defaultVie
         }
         setDiff(result);
       } catch (err) {
-        setError(String(err));
+        setError(errorMessage(err));
       } finally {
         setLoading(false);
       }
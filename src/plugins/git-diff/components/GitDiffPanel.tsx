@@ -1,10 +1,12 @@
 import { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { GitBranch } from 'lucide-react';
 import { IconButton } from '../../../components';
 import { eventBus, Events } from '../../../core';
 import { GitDiffTree } from './GitDiffTree';
-import type { DiffTabInfo } from '../../../core/types';
+import type { DiffTabInfo, LoadedFile } from '../../../core/types';
+import { errorMessage } from '../../../lib/utils';
 
 interface GitFileStatus {
   path: string;
@@ -34,7 +36,7 @@ export function GitDiffPanel() {
       const result = await invoke<GitStatus>('git_status', { repoPath: path });
       setStatus(result);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
       setStatus(null);
     } finally {
       setLoading(false);
@@ -64,10 +66,14 @@ export function GitDiffPanel() {
     const unsubFileSave = eventBus.on(Events.FILE_SAVE, () => {
       if (repoPath) loadStatus(repoPath);
     });
+    const unlistenGit = listen('git:changed', () => {
+      if (repoPath) loadStatus(repoPath);
+    });
     return () => {
       unsubRefresh();
       unsubRootChange();
       unsubFileSave();
+      unlistenGit.then((unlisten) => unlisten());
     };
   }, [loadStatus, repoPath]);
 
@@ -109,7 +115,7 @@ export function GitDiffPanel() {
       if (isUntracked) {
         originalContent = '';
         const fullPath = `${repoPath}/${file.path}`;
-        modifiedContent = await invoke<string>('read_file', { path: fullPath });
+        modifiedContent = (await invoke<LoadedFile>('load_file', { path: fullPath })).content;
       } else if (isStaged) {
         originalContent = await invoke<string>('git_show_file', { 
           repoPath, 
@@ -127,7 +133,7 @@ export function GitDiffPanel() {
           revision: 'HEAD' 
         }).catch(() => '');
         const fullPath = `${repoPath}/${file.path}`;
-        modifiedContent = await invoke<string>('read_file', { path: fullPath });
+        modifiedContent = (await invoke<LoadedFile>('load_file', { path: fullPath })).content;
       }
 
       const diffTabInfo: DiffTabInfo = {
@@ -145,7 +151,7 @@ export function GitDiffPanel() {
       eventBus.emit(Events.DIFF_TAB_OPEN, diffTabInfo);
     } catch (err) {
       console.error('Failed to open diff view:', err);
-      setError(`Failed to load diff: ${String(err)}`);
+      setError(`Failed to load diff: ${errorMessage(err)}`);
     }
   };
 
@@ -135,6 +135,8 @@ function TreeNodeItem({
         return 'A';
       case 'modified':
         return 'M';
+      case 'modeChanged':
+        return 'X';
       case 'deleted':
         return 'D';
       case 'renamed':
@@ -153,6 +155,7 @@ function TreeNodeItem({
       case 'deleted':
         return 'text-diff-removed';
       case 'modified':
+      case 'modeChanged':
         return 'text-diff-modified';
       case 'untracked':
         return 'text-diff-added';
@@ -2,6 +2,7 @@ import { useState, useEffect, useCallback, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { IconButton, Icon } from '../../../components';
 import { eventBus, Events } from '../../../core';
+import { errorMessage } from '../../../lib/utils';
 
 const DEBOUNCE_MS = 300;
 
@@ -95,7 +96,7 @@ export function SearchPanel() {
       const paths = new Set(result.matches.map(m => m.path));
       setExpandedFiles(paths);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
       setResults(null);
     } finally {
       setLoading(false);